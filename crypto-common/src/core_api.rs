@@ -1,8 +1,12 @@
 //! Low-level core API traits.
-use super::{FixedOutput, FixedOutputReset, Reset, Update};
-use block_buffer::DigestBuffer;
+use super::{FixedOutput, FixedOutputReset, Reset, ResetFrom, Update};
+#[cfg(all(feature = "zeroize", feature = "block-padding"))]
+use block_buffer::block_padding::ZeroPadding;
+use block_buffer::{Block, BlockBuffer, DigestBuffer, InvalidLength, LazyBlockBuffer};
 use core::fmt;
-use generic_array::{ArrayLength, GenericArray};
+use generic_array::{typenum::Unsigned, ArrayLength, GenericArray};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 /// Trait for types which consume data in blocks.
 #[cfg(feature = "core-api")]
@@ -33,6 +37,92 @@ pub trait FixedOutputCore: UpdateCore {
     );
 }
 
+/// Trait for block buffers which allow inspection and restoration
+/// of their internal state.
+pub trait BufferState<BlockSize: ArrayLength<u8>>: DigestBuffer<BlockSize> {
+    /// Return current cursor position.
+    fn get_pos(&self) -> usize;
+
+    /// Return copy of the internal block. Bytes after the cursor position
+    /// are set to zero.
+    fn get_block(&self) -> Block<BlockSize>;
+
+    /// Set buffer content and cursor position.
+    ///
+    /// Returns [`InvalidLength`] if `pos` is not a valid cursor position
+    /// for this buffer type.
+    fn set_block(&mut self, block: Block<BlockSize>, pos: usize) -> Result<(), InvalidLength>;
+}
+
+/// Trait for block buffers which can zeroize their content.
+///
+/// Implementation for [`BlockBuffer`] additionally requires the
+/// `block-padding` feature.
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+pub trait BufferZeroize {
+    /// Overwrite internal block with zeros using volatile writes and reset
+    /// cursor position.
+    fn zeroize_buffer(&mut self);
+}
+
+#[cfg(all(feature = "zeroize", feature = "block-padding"))]
+impl<BlockSize: ArrayLength<u8>> BufferZeroize for BlockBuffer<BlockSize> {
+    fn zeroize_buffer(&mut self) {
+        self.pad_with::<ZeroPadding>().as_mut_slice().zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<BlockSize: ArrayLength<u8>> BufferZeroize for LazyBlockBuffer<BlockSize> {
+    fn zeroize_buffer(&mut self) {
+        self.pad_zeros().as_mut_slice().zeroize();
+    }
+}
+
+impl<BlockSize: ArrayLength<u8>> BufferState<BlockSize> for BlockBuffer<BlockSize> {
+    #[inline]
+    fn get_pos(&self) -> usize {
+        BlockBuffer::get_pos(self)
+    }
+
+    fn get_block(&self) -> Block<BlockSize> {
+        let mut buffer = self.clone();
+        let mut res = Block::<BlockSize>::default();
+        buffer.digest_pad(0, &[], |block| res.copy_from_slice(block));
+        res
+    }
+
+    fn set_block(&mut self, block: Block<BlockSize>, pos: usize) -> Result<(), InvalidLength> {
+        if pos < BlockSize::USIZE {
+            self.set(block, pos);
+            Ok(())
+        } else {
+            Err(InvalidLength)
+        }
+    }
+}
+
+impl<BlockSize: ArrayLength<u8>> BufferState<BlockSize> for LazyBlockBuffer<BlockSize> {
+    #[inline]
+    fn get_pos(&self) -> usize {
+        LazyBlockBuffer::get_pos(self)
+    }
+
+    fn get_block(&self) -> Block<BlockSize> {
+        self.clone().pad_zeros().clone()
+    }
+
+    fn set_block(&mut self, block: Block<BlockSize>, pos: usize) -> Result<(), InvalidLength> {
+        if pos <= BlockSize::USIZE {
+            self.set(block, pos);
+            Ok(())
+        } else {
+            Err(InvalidLength)
+        }
+    }
+}
+
 /// Trait which stores algorithm name constant, used in `Debug` implementations.
 pub trait AlgorithmName {
     /// Write algorithm name into `f`.
@@ -49,6 +139,11 @@ pub struct CoreWrapper<T: UpdateCore> {
 }
 
 impl<T: UpdateCore> CoreWrapper<T> {
+    /// Block size of the underlying core in bytes.
+    ///
+    /// Unlike `T::BlockSize::USIZE` it can be used in `const` contexts.
+    pub const BLOCK_SIZE: usize = T::BlockSize::USIZE;
+
     /// Create new wrapper from `core`.
     #[inline]
     pub fn from_core(core: T) -> Self {
@@ -56,12 +151,134 @@ impl<T: UpdateCore> CoreWrapper<T> {
         Self { core, buffer }
     }
 
+    /// Create new wrapper from `core` and `buffer`.
+    ///
+    /// This is the inverse of [`CoreWrapper::decompose`].
+    #[inline]
+    pub fn compose(core: T, buffer: T::Buffer) -> Self {
+        Self { core, buffer }
+    }
+
     /// Decompose wrapper into inner parts.
     #[inline]
     pub fn decompose(self) -> (T, T::Buffer) {
         let Self { core, buffer } = self;
         (core, buffer)
     }
+
+    /// Get reference to the wrapped core.
+    ///
+    /// Note that the core state does not include data stored in the buffer.
+    #[inline]
+    pub fn get_core(&self) -> &T {
+        &self.core
+    }
+
+    /// Get mutable reference to the wrapped core.
+    ///
+    /// This is a low-level method. Modifications of the core state are not
+    /// synchronized with the buffer state, so incorrect use may result in
+    /// wrong hash results. Use [`CoreWrapper::decompose`] to get both parts.
+    #[inline]
+    pub fn get_core_mut(&mut self) -> &mut T {
+        &mut self.core
+    }
+
+    /// Save current state of the wrapper.
+    ///
+    /// Together with [`CoreWrapper::restore`] this method allows to process
+    /// a common prefix only once and then hash many different suffixes
+    /// using a single wrapper instance.
+    #[inline]
+    pub fn checkpoint(&self) -> Self
+    where
+        Self: Clone,
+    {
+        self.clone()
+    }
+
+    /// Rewind wrapper to a state previously saved with
+    /// [`CoreWrapper::checkpoint`].
+    #[inline]
+    pub fn restore(&mut self, checkpoint: &Self)
+    where
+        Self: Clone,
+    {
+        self.clone_from(checkpoint);
+    }
+
+    /// Get block size of the underlying core in bytes.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        T::BlockSize::USIZE
+    }
+}
+
+impl<T> CoreWrapper<T>
+where
+    T: UpdateCore,
+    T::Buffer: BufferState<T::BlockSize>,
+{
+    /// Decompose wrapper into core, buffered block and cursor position.
+    ///
+    /// Bytes of the block after the cursor position are set to zero. The
+    /// parts can be stored and later passed to [`CoreWrapper::from_parts`].
+    #[inline]
+    pub fn into_parts(self) -> (T, Block<T::BlockSize>, usize) {
+        let Self { core, buffer } = self;
+        (core, buffer.get_block(), buffer.get_pos())
+    }
+
+    /// Create new wrapper from core, buffered block and cursor position.
+    ///
+    /// Returns [`InvalidLength`] if `pos` is not a valid cursor position for
+    /// the core buffer type. Note that the core and buffer states are not
+    /// checked against each other, so combining parts of different wrappers
+    /// results in incorrect hash results, but never in memory unsafety.
+    pub fn from_parts(
+        core: T,
+        block: Block<T::BlockSize>,
+        pos: usize,
+    ) -> Result<Self, InvalidLength> {
+        let mut buffer = T::Buffer::default();
+        buffer.set_block(block, pos)?;
+        Ok(Self { core, buffer })
+    }
+
+    /// Process only the part of `data` which ends at a block boundary and
+    /// return the remaining unprocessed part.
+    ///
+    /// If the internal buffer contains a partial block, it gets completed
+    /// using the start of `data` first. Returned remainder is always shorter
+    /// than block size and it's never stored in the internal buffer, so
+    /// callers can manage it using their own staging buffer.
+    pub fn update_aligned<'a>(&mut self, data: &'a [u8]) -> &'a [u8] {
+        let bs = T::BlockSize::USIZE;
+        let pos = self.buffer.get_pos() % bs;
+        let (head, rest) = if pos != 0 {
+            data.split_at(core::cmp::min(bs - pos, data.len()))
+        } else {
+            (&[][..], data)
+        };
+        self.update(head);
+        let (blocks, tail) = rest.split_at(rest.len() - rest.len() % bs);
+        self.update(blocks);
+        tail
+    }
+
+    /// Process `data` followed by zero bytes up to the next block boundary.
+    ///
+    /// Note that the padding bytes are part of the hashed input, i.e. this
+    /// method is not related to the internal padding of the algorithm.
+    /// Block boundary is computed using total length of processed data,
+    /// not only the length of `data`.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        self.update(data);
+        let bs = T::BlockSize::USIZE;
+        let pad = (bs - self.buffer.get_pos()) % bs;
+        let zeros = Block::<T::BlockSize>::default();
+        self.update(&zeros[..pad]);
+    }
 }
 
 impl<T: UpdateCore + Reset> CoreWrapper<T> {
@@ -74,6 +291,25 @@ impl<T: UpdateCore + Reset> CoreWrapper<T> {
         buffer.reset();
         res
     }
+
+    /// Reset wrapper to its initial state and process `prefix`.
+    ///
+    /// It's intended for keyed hashing, where a key prefix is processed
+    /// after every reset. For prefixes longer than a few blocks it may be
+    /// more efficient to save a [`CoreWrapper::checkpoint`] after processing
+    /// the prefix once and [`CoreWrapper::restore`] it instead.
+    #[inline]
+    pub fn reset_to_keyed(&mut self, prefix: &[u8]) {
+        self.core.reset();
+        self.buffer.reset();
+        self.update(prefix);
+    }
+}
+
+impl<T: UpdateCore + AlgorithmName> fmt::Display for CoreWrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        T::write_alg_name(f)
+    }
 }
 
 impl<T: UpdateCore + AlgorithmName> fmt::Debug for CoreWrapper<T> {
@@ -91,6 +327,17 @@ impl<D: Reset + UpdateCore> Reset for CoreWrapper<D> {
     }
 }
 
+impl<D: UpdateCore + Clone> ResetFrom for CoreWrapper<D>
+where
+    D::Buffer: Clone,
+{
+    #[inline]
+    fn reset_from(&mut self, other: &Self) {
+        self.core.clone_from(&other.core);
+        self.buffer.clone_from(&other.buffer);
+    }
+}
+
 impl<D: UpdateCore> Update for CoreWrapper<D> {
     #[inline]
     fn update(&mut self, input: &[u8]) {
@@ -99,6 +346,20 @@ impl<D: UpdateCore> Update for CoreWrapper<D> {
     }
 }
 
+impl<D: UpdateCore> Extend<u8> for CoreWrapper<D> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        crate::update_bytes(self, iter);
+    }
+}
+
+impl<'a, D: UpdateCore> Extend<&'a [u8]> for CoreWrapper<D> {
+    fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, iter: I) {
+        for data in iter {
+            self.update(data);
+        }
+    }
+}
+
 impl<D: FixedOutputCore> FixedOutput for CoreWrapper<D> {
     type OutputSize = D::OutputSize;
 
@@ -116,6 +377,17 @@ impl<D: FixedOutputCore + Reset> FixedOutputReset for CoreWrapper<D> {
     }
 }
 
+/// Only UTF-8 bytes of written strings are processed, i.e. hash of data
+/// written using `write!(hasher, "{}", value)` is equal to hash of
+/// `value.to_string()`.
+impl<D: UpdateCore> fmt::Write for CoreWrapper<D> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        Update::update(self, s.as_bytes());
+        Ok(())
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<D: UpdateCore> std::io::Write for CoreWrapper<D> {
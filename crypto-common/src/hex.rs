@@ -0,0 +1,57 @@
+//! Hex encoding helpers shared by the hash function and MAC traits.
+
+/// Lowercase hex alphabet.
+pub const LOWER: &[u8; 16] = b"0123456789abcdef";
+/// Uppercase hex alphabet.
+pub const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Encode `data` as hex characters using `alphabet`.
+///
+/// Every byte produces two characters, the most significant nibble first.
+pub fn encode<'a>(data: &'a [u8], alphabet: &'a [u8; 16]) -> impl Iterator<Item = char> + 'a {
+    data.iter().flat_map(move |&b| {
+        let hi = char::from(alphabet[usize::from(b >> 4)]);
+        let lo = char::from(alphabet[usize::from(b & 0x0f)]);
+        core::iter::once(hi).chain(core::iter::once(lo))
+    })
+}
+
+/// Decode hex string `src` into `dst` in constant time.
+///
+/// Both lowercase and uppercase digits are accepted. Returns 1 if `src`
+/// length is equal to `2 * dst.len()` and all characters are valid hex digits,
+/// and 0 otherwise. The returned value can be converted to `subtle::Choice`.
+///
+/// Execution time depends only on length of `src` and `dst`, i.e. neither
+/// values of valid digits, nor positions of invalid characters are leaked.
+/// Content of `dst` is unspecified if the returned value is equal to 0.
+pub fn decode_ct(src: &[u8], dst: &mut [u8]) -> u8 {
+    if src.len() != 2 * dst.len() {
+        return 0;
+    }
+    let mut err = 0u16;
+    for (b, pair) in dst.iter_mut().zip(src.chunks_exact(2)) {
+        let hi = decode_nibble(pair[0]);
+        let lo = decode_nibble(pair[1]);
+        err |= (hi | lo) >> 8;
+        *b = ((hi << 4) | lo) as u8;
+    }
+    // `err` is equal to either 0 or 0xFF
+    (1 ^ (err & 1)) as u8
+}
+
+/// Decode hex digit without data-dependent branches.
+///
+/// Returns value of the digit or `0xFFFF` for invalid characters.
+#[inline(always)]
+fn decode_nibble(c: u8) -> u16 {
+    let c = i16::from(c);
+    let mut res: i16 = -1;
+    // 0-9: 0x30-0x39
+    res += (((0x2f - c) & (c - 0x3a)) >> 8) & (c - 0x2f);
+    // A-F: 0x41-0x46
+    res += (((0x40 - c) & (c - 0x47)) >> 8) & (c - 0x36);
+    // a-f: 0x61-0x66
+    res += (((0x60 - c) & (c - 0x67)) >> 8) & (c - 0x56);
+    res as u16
+}
@@ -12,20 +12,240 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+use core::ops::{Deref, DerefMut};
 use generic_array::{ArrayLength, GenericArray};
 
 #[cfg(feature = "core-api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
 pub use block_buffer;
 
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+pub use zeroize;
+
 #[cfg(feature = "core-api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
 pub mod core_api;
 
+#[cfg(feature = "hex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+pub mod hex;
+
+macro_rules! impl_int_update {
+    ($($ty:ty, $update:ident, $chain:ident, $to_bytes:ident, $enc:expr;)*) => {$(
+        #[doc = "Update state using `n` encoded as "]
+        #[doc = $enc]
+        #[doc = " integer."]
+        #[inline]
+        fn $update(&mut self, n: $ty) {
+            self.update(&n.$to_bytes());
+        }
+
+        #[doc = "Update state using `n` encoded as "]
+        #[doc = $enc]
+        #[doc = " integer in a chained manner."]
+        #[inline]
+        fn $chain(mut self, n: $ty) -> Self
+        where
+            Self: Sized,
+        {
+            self.$update(n);
+            self
+        }
+    )*};
+}
+
 /// Trait for types which consume data.
 pub trait Update {
     /// Update state using the provided data.
     fn update(&mut self, data: &[u8]);
+
+    impl_int_update!(
+        u16, update_u16_le, chain_u16_le, to_le_bytes, "a 16-bit little-endian";
+        u16, update_u16_be, chain_u16_be, to_be_bytes, "a 16-bit big-endian";
+        u32, update_u32_le, chain_u32_le, to_le_bytes, "a 32-bit little-endian";
+        u32, update_u32_be, chain_u32_be, to_be_bytes, "a 32-bit big-endian";
+        u64, update_u64_le, chain_u64_le, to_le_bytes, "a 64-bit little-endian";
+        u64, update_u64_be, chain_u64_be, to_be_bytes, "a 64-bit big-endian";
+        u128, update_u128_le, chain_u128_le, to_le_bytes, "a 128-bit little-endian";
+        u128, update_u128_be, chain_u128_be, to_be_bytes, "a 128-bit big-endian";
+    );
+
+    /// Update state using `data` prefixed with its length.
+    ///
+    /// Length is encoded as a 64-bit little-endian integer. Framing makes
+    /// sequences of variable-length fields unambiguous, e.g. sequences
+    /// `("ab", "c")` and `("a", "bc")` result in different states.
+    #[inline]
+    fn update_framed(&mut self, data: &[u8]) {
+        self.update(&(data.len() as u64).to_le_bytes());
+        self.update(data);
+    }
+
+    /// Update state using `count` copies of `byte`.
+    ///
+    /// Data is processed in chunks using a 64-byte stack buffer, which
+    /// is a multiple of block sizes used by the most hash functions.
+    #[inline]
+    fn update_repeated(&mut self, byte: u8, mut count: usize) {
+        let buf = [byte; 64];
+        while count != 0 {
+            let n = core::cmp::min(count, buf.len());
+            self.update(&buf[..n]);
+            count -= n;
+        }
+    }
+
+    /// Update state using `data` prefixed with its length in a chained manner.
+    ///
+    /// See [`Update::update_framed`] for more information.
+    #[inline]
+    fn chain_framed(mut self, data: impl AsRef<[u8]>) -> Self
+    where
+        Self: Sized,
+    {
+        self.update_framed(data.as_ref());
+        self
+    }
+
+    /// Update state using `data` in a chained manner if `cond` is `true`.
+    ///
+    /// Note that execution time depends on `cond`, so it should not be used
+    /// with secret conditions.
+    #[inline]
+    fn chain_update_if(mut self, cond: bool, data: impl AsRef<[u8]>) -> Self
+    where
+        Self: Sized,
+    {
+        if cond {
+            self.update(data.as_ref());
+        }
+        self
+    }
+
+    /// Update state using all byte slices yielded by `iter` in a chained
+    /// manner.
+    #[inline]
+    fn chain_iter<I, B>(mut self, iter: I) -> Self
+    where
+        Self: Sized,
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        for data in iter {
+            self.update(data.as_ref());
+        }
+        self
+    }
+
+    /// Update state using all byte slices yielded by `items`, each prefixed
+    /// with its length, in a chained manner.
+    ///
+    /// See [`Update::update_framed`] for more information.
+    #[inline]
+    fn chain_update_framed_many<I, B>(mut self, items: I) -> Self
+    where
+        Self: Sized,
+        I: IntoIterator<Item = B>,
+        B: AsRef<[u8]>,
+    {
+        for item in items {
+            self.update_framed(item.as_ref());
+        }
+        self
+    }
+
+    /// Update state using all slices in `bufs`.
+    ///
+    /// Slices are processed in order, i.e. the result is equal to processing
+    /// concatenation of all slices.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    fn update_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) {
+        for buf in bufs {
+            self.update(buf);
+        }
+    }
+
+    /// Update state using all remaining data in `buf` and advance it.
+    ///
+    /// Contiguous chunks returned by [`bytes::Buf::chunk`] are processed
+    /// in buffer order, i.e. the result is equal to processing concatenation
+    /// of all remaining bytes. After the call `buf` has no remaining bytes.
+    #[cfg(feature = "bytes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+    fn update_buf<B: bytes::Buf>(&mut self, buf: &mut B)
+    where
+        Self: Sized,
+    {
+        while buf.has_remaining() {
+            let chunk = buf.chunk();
+            let n = chunk.len();
+            self.update(chunk);
+            buf.advance(n);
+        }
+    }
+
+    /// Read all data from `reader` until EOF and update state using it.
+    ///
+    /// Data is read in chunks of 4 KiB, which is a multiple of block sizes
+    /// used by the most hash functions, so block-level algorithms can process
+    /// whole blocks without additional buffering.
+    ///
+    /// Returns total number of consumed bytes. I/O errors other than
+    /// [`ErrorKind::Interrupted`][std::io::ErrorKind::Interrupted] are
+    /// propagated, in this case data read before the error is already
+    /// processed.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    fn update_from_reader<R: std::io::Read + ?Sized>(
+        &mut self,
+        reader: &mut R,
+    ) -> std::io::Result<u64>
+    where
+        Self: Sized,
+    {
+        let mut buf = [0u8; 4096];
+        let mut total = 0u64;
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => return Ok(total),
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            self.update(&buf[..n]);
+            total += n as u64;
+        }
+    }
+}
+
+impl<D: Update + ?Sized> Update for &mut D {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        D::update(*self, data);
+    }
+}
+
+/// Update `hasher` state using bytes yielded by `iter`.
+///
+/// Bytes are collected into a 64-byte stack buffer and processed in chunks.
+/// Used by the `Extend<u8>` implementations of the wrapper types, it's not
+/// part of the public API.
+#[doc(hidden)]
+pub fn update_bytes<D: Update + ?Sized>(hasher: &mut D, iter: impl IntoIterator<Item = u8>) {
+    let mut buf = [0u8; 64];
+    let mut pos = 0;
+    for b in iter {
+        buf[pos] = b;
+        pos += 1;
+        if pos == buf.len() {
+            hasher.update(&buf);
+            pos = 0;
+        }
+    }
+    hasher.update(&buf[..pos]);
 }
 
 /// Trait for types which return fixed-sized result after finalization.
@@ -43,6 +263,25 @@ pub trait FixedOutput: Sized {
         self.finalize_into(&mut out);
         out
     }
+
+    /// Retrieve result of the data processed so far without consuming or
+    /// modifying the hasher instance.
+    ///
+    /// The value is cloned and the clone gets finalized, so the original
+    /// instance can be updated further.
+    ///
+    /// Note that an intermediate result of many hash functions (e.g.
+    /// Merkle–Damgård ones) is vulnerable to length extension: anyone who
+    /// knows it can compute result of the processed data concatenated with
+    /// padding and arbitrary suffix. Use this method only in protocols
+    /// which account for it.
+    #[inline]
+    fn finalize_clone(&self) -> GenericArray<u8, Self::OutputSize>
+    where
+        Self: Clone,
+    {
+        self.clone().finalize_fixed()
+    }
 }
 
 /// Trait for types which return fixed-sized result after finalization and reset
@@ -64,4 +303,55 @@ pub trait FixedOutputReset: FixedOutput + Reset {
 pub trait Reset {
     /// Reset value to its initial state.
     fn reset(&mut self);
+
+    /// Get guard which provides access to the value and resets it on drop.
+    ///
+    /// It guarantees that the value is reset even on early returns.
+    #[inline]
+    fn reset_guard(&mut self) -> ResetGuard<'_, Self>
+    where
+        Self: Sized,
+    {
+        ResetGuard { inner: self }
+    }
+}
+
+/// Trait for resetting values to a state of another value of the same type.
+pub trait ResetFrom {
+    /// Copy state of `other` into `self`.
+    ///
+    /// This is equivalent to `self.clone_from(other)`, but expresses intent,
+    /// e.g. for reusing a pool of hashers which processed a common prefix.
+    fn reset_from(&mut self, other: &Self);
+}
+
+/// Guard which resets the wrapped value on drop.
+///
+/// Created by the [`Reset::reset_guard`] method.
+#[derive(Debug)]
+pub struct ResetGuard<'a, T: Reset> {
+    inner: &'a mut T,
+}
+
+impl<'a, T: Reset> Deref for ResetGuard<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.inner
+    }
+}
+
+impl<'a, T: Reset> DerefMut for ResetGuard<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner
+    }
+}
+
+impl<'a, T: Reset> Drop for ResetGuard<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.reset();
+    }
 }
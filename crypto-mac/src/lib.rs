@@ -10,6 +10,9 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs, rust_2018_idioms)]
 
+#[cfg(feature = "hex")]
+extern crate alloc;
+
 #[cfg(feature = "std")]
 extern crate std;
 
@@ -34,8 +37,12 @@ pub use cipher::{errors::InvalidLength, FromKey};
 pub use crypto_common::{FixedOutput, FixedOutputReset, Reset, Update};
 pub use generic_array::{self, typenum::consts};
 
+#[cfg(feature = "hex")]
+use alloc::string::String;
 use core::fmt;
-use generic_array::GenericArray;
+#[cfg(feature = "hex")]
+use crypto_common::hex;
+use generic_array::{typenum::Unsigned, GenericArray};
 use subtle::{Choice, ConstantTimeEq};
 
 /// Key for an algorithm that implements [`FromKey`].
@@ -58,20 +65,138 @@ pub trait Mac: FromKey + Update + FixedOutput {
         Output::new(self.finalize_fixed_reset())
     }
 
+    /// Obtain the result of a [`Mac`] computation together with a key
+    /// commitment value and consume [`Mac`] instance.
+    ///
+    /// The commitment is a tag computed over a fixed domain separation
+    /// string using a reset copy of the instance, i.e. it depends only on
+    /// the key and not on the processed input. Protocols can transmit it
+    /// alongside the tag to bind the message to a particular key.
+    ///
+    /// The commitment is binding only if the underlying MAC is collision
+    /// resistant with respect to keys (e.g. HMAC instantiated with
+    /// a collision resistant hash function). It provides no such guarantee
+    /// for universal hash based MACs like Poly1305 or GMAC. The commitment
+    /// is equal to the tag of the domain separation string, so protocols
+    /// must not accept it as a tag of regular messages.
+    fn finalize_committing(self) -> (Output<Self>, Output<Self>)
+    where
+        Self: Clone + Reset,
+    {
+        let mut committer = self.clone();
+        committer.reset();
+        committer.update(COMMITMENT_DOMAIN);
+        (self.finalize(), committer.finalize())
+    }
+
     /// Check if tag/code value is correct for the processed input.
+    ///
+    /// Returns [`MacError::LengthMismatch`] if `tag` length is not equal to
+    /// the output size and [`MacError::TagMismatch`] if tag value is not
+    /// correct. Tag values are compared in constant time.
     fn verify(self, tag: &[u8]) -> Result<(), MacError> {
+        if tag.len() != Self::OutputSize::USIZE {
+            return Err(MacError::LengthMismatch);
+        }
         let choice = self.finalize().bytes.ct_eq(tag);
 
         if choice.unwrap_u8() == 1 {
             Ok(())
         } else {
-            Err(MacError)
+            Err(MacError::TagMismatch)
+        }
+    }
+
+    /// Obtain the result of a [`Mac`] computation as a lowercase hex string
+    /// and consume [`Mac`] instance.
+    #[cfg(feature = "hex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+    fn finalize_hex(self) -> String {
+        let tag = self.finalize_fixed();
+        let mut res = String::with_capacity(2 * tag.len());
+        res.extend(hex::encode(&tag, hex::LOWER));
+        res
+    }
+
+    /// Check if hex-encoded tag/code value is correct for the processed input.
+    ///
+    /// Both lowercase and uppercase hex digits are accepted. Returns
+    /// [`MacError::LengthMismatch`] if `tag` does not encode a full tag and
+    /// [`MacError::TagMismatch`] if `tag` is not a valid hex string or tag
+    /// value is not correct. Hex decoding and tag comparison run in constant
+    /// time, so invalid characters are not distinguished from wrong digits.
+    #[cfg(feature = "hex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+    fn verify_hex(self, tag: &str) -> Result<(), MacError> {
+        let tag = tag.as_bytes();
+        let mut decoded = GenericArray::<u8, Self::OutputSize>::default();
+        if tag.len() != 2 * decoded.len() {
+            return Err(MacError::LengthMismatch);
+        }
+        let valid = Choice::from(hex::decode_ct(tag, &mut decoded));
+        let choice = valid & self.finalize().bytes.ct_eq(&decoded);
+
+        if choice.unwrap_u8() == 1 {
+            Ok(())
+        } else {
+            Err(MacError::TagMismatch)
+        }
+    }
+
+    /// Check truncated tag/code value against the leftmost `tag.len()` bytes
+    /// of the computed tag.
+    ///
+    /// Returns [`MacError::LengthMismatch`] if `tag` is empty or longer than
+    /// the full tag.
+    fn verify_truncated_left(self, tag: &[u8]) -> Result<(), MacError> {
+        let n = tag.len();
+        if n == 0 || n > Self::OutputSize::USIZE {
+            return Err(MacError::LengthMismatch);
+        }
+        let choice = self.finalize().bytes[..n].ct_eq(tag);
+
+        if choice.unwrap_u8() == 1 {
+            Ok(())
+        } else {
+            Err(MacError::TagMismatch)
+        }
+    }
+
+    /// Check truncated tag/code value against the rightmost `tag.len()` bytes
+    /// of the computed tag.
+    ///
+    /// Returns [`MacError::LengthMismatch`] if `tag` is empty or longer than
+    /// the full tag.
+    fn verify_truncated_right(self, tag: &[u8]) -> Result<(), MacError> {
+        let n = tag.len();
+        let m = Self::OutputSize::USIZE;
+        if n == 0 || n > m {
+            return Err(MacError::LengthMismatch);
+        }
+        let choice = self.finalize().bytes[m - n..].ct_eq(tag);
+
+        if choice.unwrap_u8() == 1 {
+            Ok(())
+        } else {
+            Err(MacError::TagMismatch)
         }
     }
 }
 
 impl<T: FromKey + Update + FixedOutput> Mac for T {}
 
+/// Compute MAC tag of `data` using variable size `key`.
+///
+/// Returns [`InvalidLength`] if `key` length is not supported by `M`.
+pub fn keyed_hash<M: Mac>(key: &[u8], data: &[u8]) -> Result<Output<M>, InvalidLength> {
+    let mut mac = M::new_from_slice(key)?;
+    mac.update(data);
+    Ok(mac.finalize())
+}
+
+/// Domain separation string used by [`Mac::finalize_committing`].
+const COMMITMENT_DOMAIN: &[u8] = b"crypto-mac key commitment v1";
+
 /// [`Output`] is a thin wrapper around bytes array which provides a safe `Eq`
 /// implementation that runs in a fixed time.
 #[derive(Clone)]
@@ -110,12 +235,24 @@ impl<M: Mac> PartialEq for Output<M> {
 impl<M: Mac> Eq for Output<M> {}
 
 /// Error type for signaling failed MAC verification
-#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
-pub struct MacError;
+///
+/// Only tag length is checked in variable time, so it's safe to distinguish
+/// error variants. New variants may be added in future releases.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MacError {
+    /// Tag length is not correct.
+    LengthMismatch,
+    /// Tag value is not correct.
+    TagMismatch,
+}
 
 impl fmt::Display for MacError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("failed MAC verification")
+        f.write_str(match self {
+            MacError::LengthMismatch => "failed MAC verification: invalid tag length",
+            MacError::TagMismatch => "failed MAC verification",
+        })
     }
 }
 
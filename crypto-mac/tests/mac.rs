@@ -0,0 +1,143 @@
+use crypto_mac::{
+    consts::{U16, U8},
+    generic_array::GenericArray,
+    keyed_hash, FixedOutput, FromKey, Mac, MacError, Reset, Update,
+};
+
+/// Toy keyed FNV-1a based MAC, NOT cryptographically secure.
+#[derive(Clone)]
+struct MockMac {
+    key: u64,
+    state: u64,
+}
+
+impl FromKey for MockMac {
+    type KeySize = U8;
+
+    fn new(key: &GenericArray<u8, U8>) -> Self {
+        let mut key_bytes = [0u8; 8];
+        key_bytes.copy_from_slice(key);
+        let key = u64::from_le_bytes(key_bytes);
+        Self {
+            key,
+            state: OFFSET ^ key,
+        }
+    }
+}
+
+impl Reset for MockMac {
+    fn reset(&mut self) {
+        self.state = OFFSET ^ self.key;
+    }
+}
+
+impl Update for MockMac {
+    fn update(&mut self, data: &[u8]) {
+        for &b in data {
+            self.state = (self.state ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+impl FixedOutput for MockMac {
+    type OutputSize = U16;
+
+    fn finalize_into(self, out: &mut GenericArray<u8, U16>) {
+        out[..8].copy_from_slice(&self.state.to_le_bytes());
+        out[8..].copy_from_slice(&(!self.state).to_be_bytes());
+    }
+}
+
+const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const KEY: &[u8] = b"8 b key!";
+const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+#[test]
+fn keyed_hash_verify() {
+    let tag = keyed_hash::<MockMac>(KEY, MSG).unwrap().into_bytes();
+
+    let mut mac = MockMac::new_from_slice(KEY).unwrap();
+    mac.update(MSG);
+    assert!(mac.clone().verify(&tag).is_ok());
+    assert_eq!(mac.clone().verify(&tag[1..]), Err(MacError::LengthMismatch));
+    assert_eq!(mac.clone().verify(&[]), Err(MacError::LengthMismatch));
+
+    let mut wrong = tag;
+    wrong[15] ^= 1;
+    assert_eq!(mac.verify(&wrong), Err(MacError::TagMismatch));
+
+    assert!(keyed_hash::<MockMac>(&KEY[1..], MSG).is_err());
+}
+
+#[test]
+fn verify_truncated() {
+    let tag = keyed_hash::<MockMac>(KEY, MSG).unwrap().into_bytes();
+
+    let mut mac = MockMac::new_from_slice(KEY).unwrap();
+    mac.update(MSG);
+    for n in 1..=tag.len() {
+        assert!(mac.clone().verify_truncated_left(&tag[..n]).is_ok());
+        assert!(mac.clone().verify_truncated_right(&tag[16 - n..]).is_ok());
+    }
+
+    let mut wrong = tag;
+    wrong[3] ^= 1;
+    let res = mac.clone().verify_truncated_left(&wrong[..4]);
+    assert_eq!(res, Err(MacError::TagMismatch));
+    let res = mac.clone().verify_truncated_right(&wrong[3..]);
+    assert_eq!(res, Err(MacError::TagMismatch));
+    // mismatching byte outside of the truncated part is ignored
+    assert!(mac.clone().verify_truncated_left(&wrong[..3]).is_ok());
+    assert!(mac.clone().verify_truncated_right(&wrong[4..]).is_ok());
+
+    let long = [0u8; 17];
+    for &tag in [&[][..], &long[..]].iter() {
+        let res = mac.clone().verify_truncated_left(tag);
+        assert_eq!(res, Err(MacError::LengthMismatch));
+        let res = mac.clone().verify_truncated_right(tag);
+        assert_eq!(res, Err(MacError::LengthMismatch));
+    }
+}
+
+#[test]
+fn finalize_committing() {
+    let mut mac = MockMac::new_from_slice(KEY).unwrap();
+    mac.update(MSG);
+    let (tag, commitment) = mac.finalize_committing();
+    assert!(tag == keyed_hash::<MockMac>(KEY, MSG).unwrap());
+
+    let mut mac = MockMac::new_from_slice(KEY).unwrap();
+    mac.update(&MSG[1..]);
+    let (tag2, commitment2) = mac.finalize_committing();
+    assert!(tag != tag2);
+    assert!(commitment == commitment2);
+
+    let mac = MockMac::new_from_slice(b"8 b kez!").unwrap();
+    assert!(commitment != mac.finalize_committing().1);
+}
+
+#[cfg(feature = "hex")]
+#[test]
+fn finalize_hex() {
+    let tag = keyed_hash::<MockMac>(KEY, MSG).unwrap().into_bytes();
+    let expected: String = tag.iter().map(|b| format!("{:02x}", b)).collect();
+
+    let mut mac = MockMac::new_from_slice(KEY).unwrap();
+    mac.update(MSG);
+    let hex = mac.clone().finalize_hex();
+    assert_eq!(hex, expected);
+
+    assert!(mac.clone().verify_hex(&hex).is_ok());
+    assert!(mac.clone().verify_hex(&hex.to_uppercase()).is_ok());
+
+    let mut invalid = hex.clone();
+    invalid.replace_range(4..5, "g");
+    assert_eq!(mac.clone().verify_hex(&invalid), Err(MacError::TagMismatch));
+    invalid.replace_range(4..5, " ");
+    assert_eq!(mac.clone().verify_hex(&invalid), Err(MacError::TagMismatch));
+    let wrong = if &hex[..1] == "0" { "1" } else { "0" };
+    invalid.replace_range(..1, wrong);
+    invalid.replace_range(4..5, &hex[4..5]);
+    assert_eq!(mac.clone().verify_hex(&invalid), Err(MacError::TagMismatch));
+    assert_eq!(mac.verify_hex(&hex[2..]), Err(MacError::LengthMismatch));
+}
@@ -4,18 +4,41 @@
 //! core algorithm wrapped by the wrapper types, which implement the
 //! higher-level traits.
 use crate::InvalidOutputSize;
-use crate::{ExtendableOutput, Reset};
+use crate::{ExtendableOutput, HashMarker, Reset};
 use generic_array::{ArrayLength, GenericArray};
 
-pub use crypto_common::core_api::{AlgorithmName, CoreWrapper, FixedOutputCore, UpdateCore};
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "alloc")]
+use core::{fmt, marker::PhantomData};
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+pub use crypto_common::core_api::BufferZeroize;
+pub use crypto_common::core_api::{
+    AlgorithmName, BufferState, CoreWrapper, FixedOutputCore, UpdateCore,
+};
 
 mod ct_variable;
+mod hmac;
 mod rt_variable;
+#[cfg(feature = "const-generics")]
+mod update_many;
 mod xof_reader;
 
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+pub use ct_variable::CtVariableCore;
 pub use ct_variable::CtVariableCoreWrapper;
-pub use rt_variable::RtVariableCoreWrapper;
+pub use hmac::hmac;
+pub use rt_variable::{OpaqueDebug, RtVariableCoreWrapper};
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+pub use update_many::{MultiHasher, UpdateMany};
 pub use xof_reader::XofReaderCoreWrapper;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use xof_reader::XofReaderIoReader;
 
 /// Core trait for hash functions with extendable (XOF) output size.
 pub trait ExtendableOutputCore: UpdateCore {
@@ -34,6 +57,17 @@ pub trait XofReaderCore {
 
     /// Read next XOF block.
     fn read_block(&mut self) -> GenericArray<u8, Self::BlockSize>;
+
+    /// Fill `blocks` with consecutive XOF blocks.
+    ///
+    /// Output must be equal to calling [`XofReaderCore::read_block`] for
+    /// every block. The default implementation does exactly that, cores
+    /// which can generate several blocks at once may override it.
+    fn read_blocks(&mut self, blocks: &mut [GenericArray<u8, Self::BlockSize>]) {
+        for block in blocks {
+            *block = self.read_block();
+        }
+    }
 }
 
 /// Core trait for hash functions with variable output size.
@@ -58,6 +92,8 @@ pub trait VariableOutputCore: UpdateCore + Sized {
     );
 }
 
+impl<T: UpdateCore + HashMarker> HashMarker for CoreWrapper<T> {}
+
 impl<D: ExtendableOutputCore + Default + Reset> ExtendableOutput for CoreWrapper<D> {
     type Reader = XofReaderCoreWrapper<D::ReaderCore>;
 
@@ -78,3 +114,18 @@ impl<D: ExtendableOutputCore + Default + Reset> ExtendableOutput for CoreWrapper
         })
     }
 }
+
+/// Get algorithm name of `A` as a string.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn alg_name_to_string<A: AlgorithmName>() -> String {
+    struct AlgName<A>(PhantomData<A>);
+
+    impl<A: AlgorithmName> fmt::Display for AlgName<A> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            A::write_alg_name(f)
+        }
+    }
+
+    AlgName::<A>(PhantomData).to_string()
+}
@@ -1,5 +1,6 @@
 use super::{AlgorithmName, FixedOutputCore, Reset, UpdateCore, VariableOutputCore};
-use core::{fmt, marker::PhantomData};
+use crate::{HashMarker, InvalidOutputSize};
+use core::{any::type_name, fmt, marker::PhantomData};
 use generic_array::{
     typenum::{IsLessOrEqual, LeEq, NonZero},
     ArrayLength, GenericArray,
@@ -18,6 +19,46 @@ where
     _out: PhantomData<OutSize>,
 }
 
+/// [`CtVariableCoreWrapper`] with output size specified using const generic.
+///
+/// For example, `CtVariableCore<Sha512VarCore, 32>` is equivalent to
+/// `CtVariableCoreWrapper<Sha512VarCore, U32>`. `N` must be in the range
+/// from 1 to `T::MaxOutputSize`, otherwise a compilation error is raised.
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+pub type CtVariableCore<T, const N: usize> = CtVariableCoreWrapper<T, typenum::U<N>>;
+
+impl<T, OutSize> CtVariableCoreWrapper<T, OutSize>
+where
+    T: VariableOutputCore,
+    OutSize: ArrayLength<u8> + IsLessOrEqual<T::MaxOutputSize>,
+    LeEq<OutSize, T::MaxOutputSize>: NonZero,
+{
+    /// Create new wrapper instance.
+    ///
+    /// Returns [`InvalidOutputSize`] if the core fails to initialize with
+    /// output size equal to `OutSize`. For correct core implementations
+    /// this method never fails.
+    #[inline]
+    pub fn try_new() -> Result<Self, InvalidOutputSize> {
+        T::new(OutSize::USIZE).map(|inner| Self {
+            inner,
+            _out: PhantomData,
+        })
+    }
+
+    /// Create new wrapper from an initialized core.
+    ///
+    /// `inner` must be initialized with output size equal to `OutSize`.
+    #[inline]
+    pub(super) fn from_inner(inner: T) -> Self {
+        Self {
+            inner,
+            _out: PhantomData,
+        }
+    }
+}
+
 impl<T, OutSize> UpdateCore for CtVariableCoreWrapper<T, OutSize>
 where
     T: VariableOutputCore,
@@ -60,10 +101,13 @@ where
 {
     #[inline]
     fn default() -> Self {
-        Self {
-            inner: T::new(OutSize::USIZE).unwrap(),
-            _out: Default::default(),
-        }
+        Self::try_new().unwrap_or_else(|_| {
+            panic!(
+                "{} does not support output size {}",
+                type_name::<T>(),
+                OutSize::USIZE,
+            )
+        })
     }
 }
 
@@ -79,6 +123,34 @@ where
     }
 }
 
+impl<T, OutSize> HashMarker for CtVariableCoreWrapper<T, OutSize>
+where
+    T: VariableOutputCore + HashMarker,
+    OutSize: ArrayLength<u8> + IsLessOrEqual<T::MaxOutputSize>,
+    LeEq<OutSize, T::MaxOutputSize>: NonZero,
+{
+}
+
+impl<T, OutSize> PartialEq for CtVariableCoreWrapper<T, OutSize>
+where
+    T: VariableOutputCore + PartialEq,
+    OutSize: ArrayLength<u8> + IsLessOrEqual<T::MaxOutputSize>,
+    LeEq<OutSize, T::MaxOutputSize>: NonZero,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T, OutSize> Eq for CtVariableCoreWrapper<T, OutSize>
+where
+    T: VariableOutputCore + Eq,
+    OutSize: ArrayLength<u8> + IsLessOrEqual<T::MaxOutputSize>,
+    LeEq<OutSize, T::MaxOutputSize>: NonZero,
+{
+}
+
 impl<T, OutSize> AlgorithmName for CtVariableCoreWrapper<T, OutSize>
 where
     T: VariableOutputCore + AlgorithmName,
@@ -91,3 +163,15 @@ where
         write!(f, "{}", OutSize::USIZE)
     }
 }
+
+impl<T, OutSize> fmt::Debug for CtVariableCoreWrapper<T, OutSize>
+where
+    T: VariableOutputCore + AlgorithmName,
+    OutSize: ArrayLength<u8> + IsLessOrEqual<T::MaxOutputSize>,
+    LeEq<OutSize, T::MaxOutputSize>: NonZero,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Self::write_alg_name(f)?;
+        f.write_str(" { .. }")
+    }
+}
@@ -0,0 +1,43 @@
+use super::{CoreWrapper, FixedOutputCore};
+use crate::{Digest, Output, Update};
+use generic_array::{typenum::Unsigned, GenericArray};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5C;
+
+/// Compute HMAC (RFC 2104) of `msg` using `key` and hash function with
+/// core `T`.
+///
+/// Keys longer than the block size are hashed first, shorter keys are padded
+/// with zeros. This function is intended for the simple one-shot case, use
+/// the `hmac` crate for incremental MAC computation and verification.
+///
+/// # Panics
+/// If `key` is longer than the block size and output size of the hash
+/// function is bigger than its block size.
+pub fn hmac<T>(key: &[u8], msg: &[u8]) -> Output<CoreWrapper<T>>
+where
+    T: FixedOutputCore + Default,
+{
+    let mut padded_key = GenericArray::<u8, T::BlockSize>::default();
+    if key.len() > T::BlockSize::USIZE {
+        let hash = CoreWrapper::<T>::digest(key);
+        padded_key[..hash.len()].copy_from_slice(&hash);
+    } else {
+        padded_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut pad = padded_key.clone();
+    pad.iter_mut().for_each(|b| *b ^= IPAD);
+    let mut inner = CoreWrapper::<T>::default();
+    Update::update(&mut inner, &pad);
+    Update::update(&mut inner, msg);
+    let inner = inner.finalize();
+
+    pad.copy_from_slice(&padded_key);
+    pad.iter_mut().for_each(|b| *b ^= OPAD);
+    let mut outer = CoreWrapper::<T>::default();
+    Update::update(&mut outer, &pad);
+    Update::update(&mut outer, &inner);
+    outer.finalize()
+}
@@ -1,11 +1,37 @@
-use super::{AlgorithmName, UpdateCore, VariableOutputCore};
-use crate::{InvalidOutputSize, Reset, Update, VariableOutput};
-use core::fmt;
+use super::{
+    AlgorithmName, BufferState, CoreWrapper, CtVariableCoreWrapper, ExtendableOutputCore,
+    UpdateCore, VariableOutputCore, XofReaderCoreWrapper,
+};
+use crate::{HashMarker, InvalidOutputSize, Reset, ResetFrom, Update, VariableOutput};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
 use crypto_common::block_buffer::DigestBuffer;
-use generic_array::typenum::Unsigned;
+use generic_array::{
+    typenum::{IsLessOrEqual, LeEq, NonZero, Unsigned},
+    ArrayLength,
+};
+
+#[cfg(feature = "zeroize")]
+use super::BufferZeroize;
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+#[cfg(feature = "serde")]
+use crypto_common::block_buffer::Block;
+#[cfg(feature = "zeroize")]
+use crypto_common::zeroize::Zeroize;
+#[cfg(feature = "serde")]
+use serde::{de, ser, Deserialize, Serialize};
 
 /// Wrapper around [`VariableOutputCore`] which selects output size
 /// at run time.
+///
+/// Output size must be at least 1 byte, zero output size is rejected without
+/// initializing the core.
 #[derive(Clone)]
 pub struct RtVariableCoreWrapper<T>
 where
@@ -16,6 +42,104 @@ where
     output_size: usize,
 }
 
+impl<T> RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    /// Change output size of the hasher and reset its state.
+    ///
+    /// Returns [`InvalidOutputSize`] if `output_size` is equal to zero or
+    /// the core can not be initialized with it. In this case the wrapper
+    /// is left untouched.
+    pub fn set_output_size(&mut self, output_size: usize) -> Result<(), InvalidOutputSize> {
+        self.core = Self::new_core(output_size)?;
+        self.buffer.reset();
+        self.output_size = output_size;
+        Ok(())
+    }
+
+    /// Initialize core with `output_size` checked to be in the range from 1
+    /// to `T::MaxOutputSize`.
+    fn new_core(output_size: usize) -> Result<T, InvalidOutputSize> {
+        // invalid output sizes are rejected regardless of the core
+        if output_size == 0 || output_size > T::MaxOutputSize::USIZE {
+            return Err(InvalidOutputSize::new(output_size, T::MaxOutputSize::USIZE));
+        }
+        T::new(output_size)
+    }
+
+    /// Convert wrapper into a wrapper with output size selected at compile time.
+    ///
+    /// The conversion preserves both core and buffer states. Returns `self`
+    /// back if its output size is not equal to `OutSize`.
+    pub fn try_into_fixed<OutSize>(
+        self,
+    ) -> Result<CoreWrapper<CtVariableCoreWrapper<T, OutSize>>, Self>
+    where
+        OutSize: ArrayLength<u8> + IsLessOrEqual<T::MaxOutputSize>,
+        LeEq<OutSize, T::MaxOutputSize>: NonZero,
+    {
+        if self.output_size != OutSize::USIZE {
+            return Err(self);
+        }
+        let core = CtVariableCoreWrapper::from_inner(self.core);
+        Ok(CoreWrapper::compose(core, self.buffer))
+    }
+
+    /// Get block size of the underlying core in bytes.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        T::BlockSize::USIZE
+    }
+}
+
+impl<T> RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore,
+    T::Buffer: BufferState<T::BlockSize>,
+{
+    /// Get number of bytes currently stored in the block buffer, i.e. bytes
+    /// which were not yet processed by the core.
+    #[inline]
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.get_pos()
+    }
+
+    /// Get number of bytes which should be processed to reach the next block
+    /// boundary.
+    ///
+    /// The returned value is always in the range from 1 to block size. Feeding
+    /// data in parts of this length followed by whole blocks allows the core to
+    /// process data without additional buffering.
+    #[inline]
+    pub fn remaining_in_block(&self) -> usize {
+        let bs = T::BlockSize::USIZE;
+        bs - self.buffer.get_pos() % bs
+    }
+}
+
+impl<T> RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + ExtendableOutputCore,
+{
+    /// Finalize hasher into an extendable output reader instead of
+    /// a fixed-size buffer.
+    ///
+    /// Available only for cores which implement [`ExtendableOutputCore`].
+    /// Output of the reader is defined by the core and may depend on output
+    /// size selected during initialization.
+    pub fn finalize_xof(self) -> XofReaderCoreWrapper<T::ReaderCore> {
+        let Self {
+            mut core,
+            mut buffer,
+            ..
+        } = self;
+        let core = core.finalize_xof_core(&mut buffer);
+        let buffer = Default::default();
+        XofReaderCoreWrapper { core, buffer }
+    }
+}
+
 impl<T> Reset for RtVariableCoreWrapper<T>
 where
     T: VariableOutputCore + UpdateCore,
@@ -33,6 +157,19 @@ where
     }
 }
 
+impl<T> ResetFrom for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + Clone,
+    T::Buffer: Clone,
+{
+    #[inline]
+    fn reset_from(&mut self, other: &Self) {
+        self.core.clone_from(&other.core);
+        self.buffer.clone_from(&other.buffer);
+        self.output_size = other.output_size;
+    }
+}
+
 impl<T> Update for RtVariableCoreWrapper<T>
 where
     T: VariableOutputCore + UpdateCore,
@@ -44,6 +181,26 @@ where
     }
 }
 
+impl<T> Extend<u8> for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        crypto_common::update_bytes(self, iter);
+    }
+}
+
+impl<'a, T> Extend<&'a [u8]> for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, iter: I) {
+        for data in iter {
+            self.update(data);
+        }
+    }
+}
+
 impl<T> VariableOutput for RtVariableCoreWrapper<T>
 where
     T: VariableOutputCore + UpdateCore,
@@ -52,7 +209,7 @@ where
 
     fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
         let buffer = Default::default();
-        T::new(output_size).map(|core| Self {
+        Self::new_core(output_size).map(|core| Self {
             core,
             buffer,
             output_size,
@@ -83,6 +240,18 @@ where
     }
 }
 
+impl<T> HashMarker for RtVariableCoreWrapper<T> where T: VariableOutputCore + HashMarker {}
+
+impl<T> fmt::Display for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + AlgorithmName,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        T::write_alg_name(f)?;
+        write!(f, "_{}", self.output_size)
+    }
+}
+
 impl<T> fmt::Debug for RtVariableCoreWrapper<T>
 where
     T: VariableOutputCore + UpdateCore + AlgorithmName,
@@ -93,6 +262,95 @@ where
     }
 }
 
+/// Wrapper around [`RtVariableCoreWrapper`] which implements [`fmt::Debug`]
+/// for cores which do not implement [`AlgorithmName`].
+///
+/// It prints `RtVariableCoreWrapper { output_size: N, .. }`, so it can be used
+/// as a field of structs which derive `Debug`. Wrappers over cores which
+/// implement [`AlgorithmName`] should be used directly, since their `Debug`
+/// implementation includes the algorithm name.
+pub struct OpaqueDebug<T>(pub RtVariableCoreWrapper<T>)
+where
+    T: VariableOutputCore + UpdateCore;
+
+impl<T> OpaqueDebug<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    /// Return the wrapped hasher.
+    pub fn into_inner(self) -> RtVariableCoreWrapper<T> {
+        self.0
+    }
+}
+
+impl<T> Clone for OpaqueDebug<T>
+where
+    T: VariableOutputCore + UpdateCore,
+    RtVariableCoreWrapper<T>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for OpaqueDebug<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    type Target = RtVariableCoreWrapper<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for OpaqueDebug<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Update for OpaqueDebug<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    #[inline]
+    fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+}
+
+impl<T> fmt::Debug for OpaqueDebug<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "RtVariableCoreWrapper {{ output_size: {}, .. }}",
+            self.0.output_size
+        )
+    }
+}
+
+/// See the [`CoreWrapper`] implementation.
+impl<T> fmt::Write for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore,
+{
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        Update::update(self, s.as_bytes());
+        Ok(())
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<T> std::io::Write for RtVariableCoreWrapper<T>
@@ -110,3 +368,193 @@ where
         Ok(())
     }
 }
+
+#[cfg(feature = "futures-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-io")))]
+impl<T> futures_io::AsyncWrite for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + Unpin,
+    T::Buffer: Unpin,
+{
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        Update::update(self.get_mut(), buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<futures_io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<futures_io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl<T> tokio::io::AsyncWrite for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + Unpin,
+    T::Buffer: Unpin,
+{
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<tokio::io::Result<usize>> {
+        Update::update(self.get_mut(), buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<tokio::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<tokio::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Zeroize core state and bytes stored in the block buffer. Output size
+/// is left intact.
+///
+/// `ZeroizeOnDrop` is not implemented since a `Drop` impl would prevent
+/// finalization methods from moving core and buffer out of the wrapper.
+/// Use [`zeroize::Zeroizing`] if zeroization on drop is required.
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T> Zeroize for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + Zeroize,
+    T::Buffer: BufferZeroize,
+{
+    fn zeroize(&mut self) {
+        self.core.zeroize();
+        self.buffer.zeroize_buffer();
+    }
+}
+
+/// Serialized as a tuple of the core state, output size, and bytes
+/// currently stored in the block buffer.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T> Serialize for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + Serialize,
+    T::Buffer: BufferState<T::BlockSize>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let block = self.buffer.get_block();
+        let data = BufferedData(&block[..self.buffer.get_pos()]);
+        (&self.core, self.output_size, data).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, T> Deserialize<'de> for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + Deserialize<'de>,
+    T::Buffer: BufferState<T::BlockSize>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use de::Error;
+
+        let (core, output_size, data): (T, usize, BufferedBlock<T::BlockSize>) =
+            Deserialize::deserialize(deserializer)?;
+        // run the same check as `new`, the deserialized core state is kept
+        Self::new_core(output_size).map_err(D::Error::custom)?;
+        let mut buffer = T::Buffer::default();
+        buffer
+            .set_block(data.block, data.pos)
+            .map_err(D::Error::custom)?;
+        Ok(Self {
+            core,
+            buffer,
+            output_size,
+        })
+    }
+}
+
+/// Helper for serializing buffered bytes.
+#[cfg(feature = "serde")]
+struct BufferedData<'a>(&'a [u8]);
+
+#[cfg(feature = "serde")]
+impl<'a> Serialize for BufferedData<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Helper for deserializing buffered bytes into a block without
+/// heap allocations.
+#[cfg(feature = "serde")]
+struct BufferedBlock<N: ArrayLength<u8>> {
+    block: Block<N>,
+    pos: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N: ArrayLength<u8>> Deserialize<'de> for BufferedBlock<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct BlockVisitor<N: ArrayLength<u8>>(core::marker::PhantomData<N>);
+
+        impl<'de, N: ArrayLength<u8>> de::Visitor<'de> for BlockVisitor<N> {
+            type Value = BufferedBlock<N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "at most {} bytes", N::USIZE)
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                if v.len() > N::USIZE {
+                    return Err(E::invalid_length(v.len(), &self));
+                }
+                let mut block = Block::<N>::default();
+                block[..v.len()].copy_from_slice(v);
+                Ok(BufferedBlock {
+                    block,
+                    pos: v.len(),
+                })
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut block = Block::<N>::default();
+                let mut pos = 0;
+                while let Some(b) = seq.next_element::<u8>()? {
+                    if pos == N::USIZE {
+                        return Err(de::Error::invalid_length(pos + 1, &self));
+                    }
+                    block[pos] = b;
+                    pos += 1;
+                }
+                Ok(BufferedBlock { block, pos })
+            }
+        }
+
+        deserializer.deserialize_bytes(BlockVisitor(Default::default()))
+    }
+}
@@ -0,0 +1,136 @@
+use super::{BufferState, FixedOutputCore, UpdateCore};
+use crypto_common::block_buffer::{Block, DigestBuffer};
+use generic_array::{typenum::Unsigned, GenericArray};
+
+/// Core trait for hash functions which can process blocks of several
+/// independent messages at once, e.g. using SIMD lanes.
+///
+/// The default implementation processes lanes sequentially, so cores without
+/// a parallel implementation can use an empty `impl` block to be usable
+/// with [`MultiHasher`].
+pub trait UpdateMany<const LANES: usize>: UpdateCore + Sized {
+    /// Update state of each core in `cores` using blocks with the same index.
+    ///
+    /// Slices in `blocks` may have different lengths, including zero.
+    fn update_many(cores: &mut [Self; LANES], blocks: [&[Block<Self::BlockSize>]; LANES]) {
+        for (core, blocks) in cores.iter_mut().zip(blocks.iter()) {
+            if !blocks.is_empty() {
+                core.update_blocks(blocks);
+            }
+        }
+    }
+}
+
+/// Wrapper which computes `LANES` independent hashes using [`UpdateMany`].
+///
+/// It handles data buffering for each lane and passes full blocks of all
+/// lanes to [`UpdateMany::update_many`] together.
+#[derive(Clone)]
+pub struct MultiHasher<T: UpdateMany<LANES>, const LANES: usize> {
+    cores: [T; LANES],
+    buffers: [T::Buffer; LANES],
+}
+
+impl<T, const LANES: usize> Default for MultiHasher<T, LANES>
+where
+    T: UpdateMany<LANES> + Default,
+    T::Buffer: BufferState<T::BlockSize>,
+{
+    fn default() -> Self {
+        Self {
+            cores: [(); LANES].map(|_| T::default()),
+            buffers: [(); LANES].map(|_| Default::default()),
+        }
+    }
+}
+
+impl<T, const LANES: usize> MultiHasher<T, LANES>
+where
+    T: UpdateMany<LANES>,
+    T::Buffer: BufferState<T::BlockSize>,
+{
+    /// Create new wrapper from lane cores.
+    pub fn from_cores(cores: [T; LANES]) -> Self {
+        Self {
+            cores,
+            buffers: [(); LANES].map(|_| Default::default()),
+        }
+    }
+
+    /// Update state of lane `i` using `inputs[i]`.
+    pub fn update(&mut self, mut inputs: [&[u8]; LANES]) {
+        let bs = T::BlockSize::USIZE;
+        let mut staged = [(); LANES].map(|_| Block::<T::BlockSize>::default());
+        let mut is_staged = [false; LANES];
+
+        // Fill partially filled buffers, so remaining data starts
+        // at a block boundary
+        let lanes = self.cores.iter_mut().zip(self.buffers.iter_mut());
+        let staging = staged.iter_mut().zip(is_staged.iter_mut());
+        for (((core, buffer), input), (block, is_staged)) in
+            lanes.zip(inputs.iter_mut()).zip(staging)
+        {
+            let pos = buffer.get_pos();
+            if pos != 0 && pos != bs {
+                let n = core::cmp::min(bs - pos, input.len());
+                let (head, tail) = input.split_at(n);
+                buffer.digest_blocks(head, |blocks| core.update_blocks(blocks));
+                *input = tail;
+            }
+            // Lazy buffers may hold a full block which must be processed
+            // before the rest of the data
+            if buffer.get_pos() == bs && !input.is_empty() {
+                *block = buffer.get_block();
+                *is_staged = true;
+                buffer.reset();
+            }
+        }
+
+        loop {
+            let staging = staged.iter_mut().zip(is_staged.iter_mut());
+            for (input, (block, is_staged)) in inputs.iter_mut().zip(staging) {
+                // The last block is always passed to the buffer, so
+                // lazy buffers work correctly
+                if !*is_staged && input.len() > bs {
+                    let (head, tail) = input.split_at(bs);
+                    block.copy_from_slice(head);
+                    *is_staged = true;
+                    *input = tail;
+                }
+            }
+            if !is_staged.contains(&true) {
+                break;
+            }
+
+            let mut blocks: [&[Block<T::BlockSize>]; LANES] = [&[]; LANES];
+            let staging = staged.iter().zip(is_staged.iter());
+            for (blocks, (block, _)) in blocks.iter_mut().zip(staging).filter(|(_, (_, &s))| s) {
+                *blocks = core::slice::from_ref(block);
+            }
+            T::update_many(&mut self.cores, blocks);
+            is_staged = [false; LANES];
+        }
+
+        let lanes = self.cores.iter_mut().zip(self.buffers.iter_mut());
+        for ((core, buffer), input) in lanes.zip(inputs.iter()) {
+            buffer.digest_blocks(input, |blocks| core.update_blocks(blocks));
+        }
+    }
+
+    /// Retrieve results of all lanes and consume the wrapper.
+    pub fn finalize(self) -> [GenericArray<u8, T::OutputSize>; LANES]
+    where
+        T: FixedOutputCore,
+    {
+        let Self {
+            mut cores,
+            mut buffers,
+        } = self;
+        let mut res = [(); LANES].map(|_| GenericArray::default());
+        let lanes = cores.iter_mut().zip(buffers.iter_mut());
+        for ((core, buffer), out) in lanes.zip(res.iter_mut()) {
+            core.finalize_fixed_core(buffer, out);
+        }
+        res
+    }
+}
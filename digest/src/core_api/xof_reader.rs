@@ -1,7 +1,7 @@
 use super::{AlgorithmName, XofReaderCore};
 use crate::XofReader;
 use core::fmt;
-use crypto_common::block_buffer::BlockBuffer;
+use crypto_common::block_buffer::{Block, BlockBuffer};
 
 /// Wrapper around [`XofReaderCore`] implementations.
 ///
@@ -19,6 +19,24 @@ impl<T: XofReaderCore + AlgorithmName> fmt::Debug for XofReaderCoreWrapper<T> {
     }
 }
 
+impl<T: XofReaderCore> XofReaderCoreWrapper<T> {
+    /// Fill `blocks` with output bytes.
+    ///
+    /// Output is equal to output of [`XofReader::read`] called on the
+    /// concatenation of `blocks`. If the wrapper does not store leftover
+    /// bytes of a previously generated block, `blocks` are passed directly
+    /// to [`XofReaderCore::read_blocks`].
+    pub fn read_blocks(&mut self, blocks: &mut [Block<T::BlockSize>]) {
+        if self.buffer.get_pos() == 0 {
+            self.core.read_blocks(blocks);
+        } else {
+            for block in blocks {
+                XofReader::read(self, block);
+            }
+        }
+    }
+}
+
 impl<R: XofReaderCore> XofReader for XofReaderCoreWrapper<R> {
     #[inline]
     fn read(&mut self, buffer: &mut [u8]) {
@@ -36,3 +54,39 @@ impl<R: XofReaderCore> std::io::Read for XofReaderCoreWrapper<R> {
         Ok(buf.len())
     }
 }
+
+/// Adapter which implements [`std::io::Read`] for any [`XofReader`].
+///
+/// Since XOF readers never run out of output, the `read` method always
+/// fills the whole buffer and never returns 0 for a non-empty buffer.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Clone, Debug, Default)]
+pub struct XofReaderIoReader<R: XofReader> {
+    reader: R,
+}
+
+#[cfg(feature = "std")]
+impl<R: XofReader> XofReaderIoReader<R> {
+    /// Create new adapter around `reader`.
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Return the wrapped reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<R: XofReader> std::io::Read for XofReaderIoReader<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        XofReader::read(&mut self.reader, buf);
+        Ok(buf.len())
+    }
+}
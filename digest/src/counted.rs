@@ -0,0 +1,78 @@
+use crate::{FixedOutput, FixedOutputReset, HashMarker, Reset, Update};
+use generic_array::GenericArray;
+
+/// Wrapper which counts number of bytes processed by the wrapped hasher.
+///
+/// It implements the same traits as the wrapped hasher, so it can be used
+/// in place of the original hasher, e.g. with [`Digest`][crate::Digest].
+/// The counter is reset together with the hasher state.
+#[derive(Clone, Debug, Default)]
+pub struct Counted<D> {
+    inner: D,
+    count: u64,
+}
+
+impl<D> Counted<D> {
+    /// Wrap `inner` hasher.
+    pub fn new(inner: D) -> Self {
+        Self { inner, count: 0 }
+    }
+
+    /// Get number of bytes processed since creation or last reset.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Return the wrapped hasher.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    /// Retrieve result and number of processed bytes, and consume the wrapper.
+    ///
+    /// It can be used by protocols which need both the digest and the message
+    /// length, e.g. to write a length field, without maintaining a separate
+    /// counter.
+    pub fn finalize_with_count(self) -> (GenericArray<u8, D::OutputSize>, u64)
+    where
+        D: FixedOutput,
+    {
+        (self.inner.finalize_fixed(), self.count)
+    }
+}
+
+impl<D: Update> Update for Counted<D> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.count += data.len() as u64;
+        self.inner.update(data);
+    }
+}
+
+impl<D: HashMarker> HashMarker for Counted<D> {}
+
+impl<D: Reset> Reset for Counted<D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.count = 0;
+    }
+}
+
+impl<D: FixedOutput> FixedOutput for Counted<D> {
+    type OutputSize = D::OutputSize;
+
+    #[inline]
+    fn finalize_into(self, out: &mut GenericArray<u8, Self::OutputSize>) {
+        self.inner.finalize_into(out);
+    }
+}
+
+impl<D: FixedOutputReset> FixedOutputReset for Counted<D> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut GenericArray<u8, Self::OutputSize>) {
+        self.inner.finalize_into_reset(out);
+        self.count = 0;
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{ExtendableOutput, Reset, Update};
+
+/// Wrapper which prepends function name and customization string to
+/// the input of an extendable-output function.
+///
+/// Both strings are prefixed with their length encoded as an 8-byte
+/// little-endian integer (see [`Update::update_framed`]) and processed
+/// before the user input. Note that this wrapper only handles generic
+/// prefix framing, so results are NOT compatible with algorithms which
+/// define their own customization, e.g. cSHAKE. Proper support of such
+/// algorithms requires support from the underlying core.
+#[derive(Clone, Debug)]
+pub struct CustomizedXof<X> {
+    hasher: X,
+    initial: X,
+}
+
+impl<X: ExtendableOutput + Clone> CustomizedXof<X> {
+    /// Create new customized XOF instance.
+    pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        let mut initial = X::default();
+        initial.update_framed(function_name);
+        initial.update_framed(customization);
+        Self {
+            hasher: initial.clone(),
+            initial,
+        }
+    }
+}
+
+impl<X: ExtendableOutput + Clone> Default for CustomizedXof<X> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(&[], &[])
+    }
+}
+
+impl<X: Update> Update for CustomizedXof<X> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+}
+
+impl<X: Clone> Reset for CustomizedXof<X> {
+    #[inline]
+    fn reset(&mut self) {
+        self.hasher.clone_from(&self.initial);
+    }
+}
+
+impl<X: ExtendableOutput + Clone> ExtendableOutput for CustomizedXof<X> {
+    type Reader = X::Reader;
+
+    #[inline]
+    fn finalize_xof(self) -> Self::Reader {
+        self.hasher.finalize_xof()
+    }
+
+    #[inline]
+    fn finalize_xof_reset(&mut self) -> Self::Reader {
+        let hasher = core::mem::replace(&mut self.hasher, self.initial.clone());
+        hasher.finalize_xof()
+    }
+}
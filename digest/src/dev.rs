@@ -33,7 +33,7 @@ macro_rules! new_test {
 
 /// Module to separate Digest from other traits
 mod foo {
-    use super::super::{Digest, Reset};
+    use super::super::{Digest, FixedOutputReset, Reset};
     use core::fmt::Debug;
 
     /// Digest test
@@ -93,9 +93,109 @@ mod foo {
         let out = sh.finalize();
         assert_eq!(out[..], expected[..]);
     }
+
+    /// Check that reset hasher behaves like a fresh instance.
+    ///
+    /// It asserts that hashing `a`, resetting and hashing `b` produces
+    /// the same result as hashing `b` with a fresh instance, and that
+    /// `finalize_reset` is equivalent to `finalize` followed by creation
+    /// of a fresh instance.
+    pub fn reset_equivalence<D>(a: &[u8], b: &[u8])
+    where
+        D: Digest + FixedOutputReset + Debug + Clone,
+    {
+        let expected_a = D::digest(a);
+        let expected_b = D::digest(b);
+
+        let mut hasher = D::new();
+        hasher.update(a);
+        hasher.reset();
+        hasher.update(b);
+        assert_eq!(hasher.finalize()[..], expected_b[..], "hash after reset");
+
+        let mut hasher = D::new();
+        hasher.update(a);
+        let clone = hasher.clone();
+        assert_eq!(
+            hasher.finalize_reset()[..],
+            expected_a[..],
+            "finalize_reset"
+        );
+        assert_eq!(clone.finalize()[..], expected_a[..], "finalize of clone");
+        hasher.update(b);
+        assert_eq!(
+            hasher.finalize_reset()[..],
+            expected_b[..],
+            "hash after finalize_reset",
+        );
+        assert_eq!(hasher.finalize()[..], D::digest(b"")[..], "empty hash");
+    }
+
+    /// Check that result does not depend on how `data` is split into chunks.
+    ///
+    /// It asserts that hashing `data` with a single update is equivalent
+    /// to hashing it split into two parts at every possible boundary and
+    /// to hashing it byte-by-byte.
+    pub fn update_chunking_invariant<D>(data: &[u8])
+    where
+        D: Digest + Debug + Clone,
+    {
+        let expected = D::digest(data);
+        let empty = D::new();
+        for i in 0..=data.len() {
+            let (a, b) = data.split_at(i);
+            let mut hasher = empty.clone();
+            hasher.update(a);
+            hasher.update(b);
+            assert_eq!(hasher.finalize()[..], expected[..], "split at {}", i);
+        }
+
+        let mut hasher = empty;
+        for chunk in data.chunks(1) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize()[..], expected[..], "byte-by-byte");
+    }
 }
 
-pub use self::foo::{digest_test, one_million_a};
+pub use self::foo::{digest_test, one_million_a, reset_equivalence, update_chunking_invariant};
+
+/// Check that reset variable-output hasher behaves like a fresh instance.
+///
+/// It asserts that hashing `a`, resetting and hashing `b` produces the same
+/// result as hashing `b` with a fresh instance and that
+/// `finalize_variable_reset` keeps the output size. `output_size` must not
+/// be bigger than 128.
+pub fn variable_reset_equivalence<D>(a: &[u8], b: &[u8], output_size: usize)
+where
+    D: VariableOutput + Debug + Clone,
+{
+    let mut expected_buf = [0u8; 128];
+    let expected = &mut expected_buf[..output_size];
+    D::digest_variable(b, expected).unwrap();
+    let mut buf = [0u8; 128];
+    let buf = &mut buf[..output_size];
+
+    let mut hasher = D::new(output_size).unwrap();
+    hasher.update(a);
+    hasher.reset();
+    assert_eq!(hasher.output_size(), output_size, "output size after reset");
+    hasher.update(b);
+    hasher.finalize_variable(|res| buf.copy_from_slice(res));
+    assert_eq!(buf, expected, "hash after reset");
+
+    let mut hasher = D::new(output_size).unwrap();
+    hasher.update(a);
+    hasher.finalize_variable_reset(|_| ());
+    assert_eq!(
+        hasher.output_size(),
+        output_size,
+        "output size after finalize_variable_reset",
+    );
+    hasher.update(b);
+    hasher.finalize_variable_reset(|res| buf.copy_from_slice(res));
+    assert_eq!(buf, expected, "hash after finalize_variable_reset");
+}
 
 /// XOF test
 pub fn xof_test<D>(input: &[u8], output: &[u8]) -> Option<&'static str>
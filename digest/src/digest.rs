@@ -1,7 +1,19 @@
-use super::{FixedOutput, FixedOutputReset, Update};
+use super::{FixedOutput, FixedOutputReset, HashMarker, InvalidBufferLength, Update};
 use generic_array::typenum::Unsigned;
 use generic_array::{ArrayLength, GenericArray};
 
+#[cfg(feature = "hex")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "hex")]
+use crypto_common::hex;
+
+#[cfg(feature = "subtle")]
+use crate::DigestMismatch;
+#[cfg(feature = "subtle")]
+use subtle::ConstantTimeEq;
+
 /// The `Digest` trait specifies an interface common for digest functions.
 ///
 /// It's a convenience wrapper around [`Update`], [`FixedOutput`],
@@ -12,9 +24,25 @@ pub trait Digest {
     /// Output size for `Digest`
     type OutputSize: ArrayLength<u8>;
 
+    /// Output size in bytes.
+    ///
+    /// Unlike `Self::OutputSize::USIZE` it can be used in `const` contexts,
+    /// e.g. for sizing arrays with a concrete hasher type.
+    const OUTPUT_SIZE: usize = Self::OutputSize::USIZE;
+
     /// Create new hasher instance
     fn new() -> Self;
 
+    /// Create new hasher instance with the `domain` separation tag
+    /// processed before any other data.
+    ///
+    /// The tag is prefixed with its length encoded as an 8-byte little-endian
+    /// integer (see [`Update::update_framed`]), so different tags can not
+    /// produce ambiguous inputs.
+    fn new_with_domain(domain: &[u8]) -> Self
+    where
+        Self: HashMarker;
+
     /// Process data, updating the internal state.
     fn update(&mut self, data: impl AsRef<[u8]>);
 
@@ -24,9 +52,21 @@ pub trait Digest {
     /// Retrieve result and consume hasher instance.
     fn finalize(self) -> Output<Self>;
 
+    /// Retrieve result and replace hasher instance with a fresh one.
+    ///
+    /// It's equivalent to [`core::mem::take`] followed by finalization, i.e.
+    /// unlike [`Digest::finalize_reset`] it does not require [`FixedOutputReset`].
+    fn take(&mut self) -> Output<Self>;
+
     /// Write result into provided array and consume the hasher instance.
     fn finalize_into(self, out: &mut Output<Self>);
 
+    /// Write result into provided slice and consume the hasher instance.
+    ///
+    /// Returns [`InvalidBufferLength`] if `out` length is not equal to
+    /// the output size.
+    fn finalize_into_slice(self, out: &mut [u8]) -> Result<(), InvalidBufferLength>;
+
     /// Retrieve result and reset hasher instance.
     fn finalize_reset(&mut self) -> Output<Self>
     where
@@ -42,6 +82,57 @@ pub trait Digest {
 
     /// Compute hash of `data`.
     fn digest(data: impl AsRef<[u8]>) -> Output<Self>;
+
+    /// Compute hash of `data` and write it into `out`.
+    ///
+    /// Returns [`InvalidBufferLength`] without processing `data` if `out`
+    /// length is not equal to the output size.
+    fn digest_into_slice(data: &[u8], out: &mut [u8]) -> Result<(), InvalidBufferLength>;
+
+    /// Retrieve result as a lowercase hex string and consume hasher instance.
+    ///
+    /// Note that [`Output`] implements [`LowerHex`][core::fmt::LowerHex] and
+    /// [`UpperHex`][core::fmt::UpperHex], so it can be hex-encoded using
+    /// `format!("{:x}", output)`.
+    #[cfg(feature = "hex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+    fn finalize_hex(self) -> String;
+
+    /// Retrieve result as an uppercase hex string and consume hasher instance.
+    #[cfg(feature = "hex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+    fn finalize_hex_upper(self) -> String;
+
+    /// Retrieve result as a lowercase hex string and reset hasher instance.
+    #[cfg(feature = "hex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hex")))]
+    fn finalize_hex_reset(&mut self) -> String
+    where
+        Self: FixedOutputReset;
+
+    /// Compute hash of concatenation of byte slices yielded by `data`.
+    ///
+    /// For an empty iterator the result is equal to hash of an empty message.
+    fn digest_iter<I>(data: I) -> Output<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>;
+
+    /// Check that result is equal to `expected` and consume hasher instance.
+    ///
+    /// Comparison runs in constant time.
+    #[cfg(feature = "subtle")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "subtle")))]
+    fn verify(self, expected: &Output<Self>) -> Result<(), DigestMismatch>;
+
+    /// Check that result is equal to `expected` and reset hasher instance.
+    ///
+    /// Comparison runs in constant time.
+    #[cfg(feature = "subtle")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "subtle")))]
+    fn verify_reset(&mut self, expected: &Output<Self>) -> Result<(), DigestMismatch>
+    where
+        Self: FixedOutputReset;
 }
 
 impl<D: FixedOutput + Default + Update> Digest for D {
@@ -52,6 +143,16 @@ impl<D: FixedOutput + Default + Update> Digest for D {
         Self::default()
     }
 
+    #[inline]
+    fn new_with_domain(domain: &[u8]) -> Self
+    where
+        Self: HashMarker,
+    {
+        let mut hasher = Self::default();
+        hasher.update_framed(domain);
+        hasher
+    }
+
     #[inline]
     fn update(&mut self, data: impl AsRef<[u8]>) {
         Update::update(self, data.as_ref());
@@ -68,11 +169,25 @@ impl<D: FixedOutput + Default + Update> Digest for D {
         FixedOutput::finalize_fixed(self)
     }
 
+    #[inline]
+    fn take(&mut self) -> Output<Self> {
+        FixedOutput::finalize_fixed(core::mem::take(self))
+    }
+
     #[inline]
     fn finalize_into(self, out: &mut Output<Self>) {
         FixedOutput::finalize_into(self, out);
     }
 
+    #[inline]
+    fn finalize_into_slice(self, out: &mut [u8]) -> Result<(), InvalidBufferLength> {
+        if out.len() != Self::OutputSize::USIZE {
+            return Err(InvalidBufferLength);
+        }
+        FixedOutput::finalize_into(self, GenericArray::from_mut_slice(out));
+        Ok(())
+    }
+
     #[inline]
     fn finalize_reset(&mut self) -> Output<Self>
     where
@@ -100,7 +215,170 @@ impl<D: FixedOutput + Default + Update> Digest for D {
         hasher.update(data.as_ref());
         hasher.finalize()
     }
+
+    #[inline]
+    fn digest_into_slice(data: &[u8], out: &mut [u8]) -> Result<(), InvalidBufferLength> {
+        if out.len() != Self::OutputSize::USIZE {
+            return Err(InvalidBufferLength);
+        }
+        let mut hasher = Self::default();
+        hasher.update(data);
+        FixedOutput::finalize_into(hasher, GenericArray::from_mut_slice(out));
+        Ok(())
+    }
+
+    #[cfg(feature = "hex")]
+    #[inline]
+    fn finalize_hex(self) -> String {
+        to_hex(&Digest::finalize(self), hex::LOWER)
+    }
+
+    #[cfg(feature = "hex")]
+    #[inline]
+    fn finalize_hex_upper(self) -> String {
+        to_hex(&Digest::finalize(self), hex::UPPER)
+    }
+
+    #[cfg(feature = "hex")]
+    #[inline]
+    fn finalize_hex_reset(&mut self) -> String
+    where
+        Self: FixedOutputReset,
+    {
+        to_hex(&Digest::finalize_reset(self), hex::LOWER)
+    }
+
+    #[inline]
+    fn digest_iter<I>(data: I) -> Output<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut hasher = Self::default();
+        for chunk in data {
+            hasher.update(chunk.as_ref());
+        }
+        hasher.finalize()
+    }
+
+    #[cfg(feature = "subtle")]
+    #[inline]
+    fn verify(self, expected: &Output<Self>) -> Result<(), DigestMismatch> {
+        ct_verify(&Digest::finalize(self), expected)
+    }
+
+    #[cfg(feature = "subtle")]
+    #[inline]
+    fn verify_reset(&mut self, expected: &Output<Self>) -> Result<(), DigestMismatch>
+    where
+        Self: FixedOutputReset,
+    {
+        ct_verify(&Digest::finalize_reset(self), expected)
+    }
+}
+
+#[cfg(feature = "hex")]
+fn to_hex(data: &[u8], alphabet: &[u8; 16]) -> String {
+    let mut res = String::with_capacity(2 * data.len());
+    res.extend(hex::encode(data, alphabet));
+    res
+}
+
+/// Compare two digests in constant time.
+///
+/// Comparison using `==` may exit on the first mismatching byte, so its
+/// execution time can leak information about digests, e.g. allow an attacker
+/// to forge a MAC tag or an integrity digest byte-by-byte. This function
+/// always inspects all bytes of both digests.
+#[cfg(feature = "subtle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "subtle")))]
+#[inline]
+pub fn ct_eq<D: Digest>(a: &Output<D>, b: &Output<D>) -> subtle::Choice {
+    a.as_slice().ct_eq(b.as_slice())
+}
+
+#[cfg(feature = "subtle")]
+fn ct_verify<N: ArrayLength<u8>>(
+    res: &GenericArray<u8, N>,
+    expected: &GenericArray<u8, N>,
+) -> Result<(), DigestMismatch> {
+    if res.as_slice().ct_eq(expected.as_slice()).into() {
+        Ok(())
+    } else {
+        Err(DigestMismatch)
+    }
 }
 
 /// Fixed of fixed-sized hash-function used by [`Digest`] methods.
 pub type Output<D> = GenericArray<u8, <D as Digest>::OutputSize>;
+
+/// Compute hash of concatenation of byte slices yielded by `iter` using `D`.
+///
+/// Equivalent to [`Digest::digest_iter`], but allows to specify hash function
+/// in the turbofish position, e.g. `collect_digest::<Sha256, _, _>(chunks)`.
+#[inline]
+pub fn collect_digest<D, I, B>(iter: I) -> Output<D>
+where
+    D: Digest,
+    I: IntoIterator<Item = B>,
+    B: AsRef<[u8]>,
+{
+    D::digest_iter(iter)
+}
+
+/// Create [`Output`] of `D` from `slice`.
+///
+/// Returns [`InvalidBufferLength`] if `slice` length is not equal to
+/// the output size of `D`.
+#[inline]
+pub fn output_from_slice<D: Digest>(slice: &[u8]) -> Result<Output<D>, InvalidBufferLength> {
+    if slice.len() == D::OutputSize::USIZE {
+        Ok(GenericArray::clone_from_slice(slice))
+    } else {
+        Err(InvalidBufferLength)
+    }
+}
+
+/// Plain array of `N` bytes, an alternative to [`Output`] which does not
+/// depend on `typenum`.
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+pub type ConstOutput<const N: usize> = [u8; N];
+
+/// Copy `out` into a plain array of length `N`.
+///
+/// Returns `None` if `N` is not equal to the output size of `D`.
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+#[inline]
+pub fn to_array<D: Digest, const N: usize>(out: &Output<D>) -> Option<ConstOutput<N>> {
+    if N != D::OutputSize::USIZE {
+        return None;
+    }
+    let mut res = [0u8; N];
+    res.copy_from_slice(out);
+    Some(res)
+}
+
+/// Compute hashes of all byte slices yielded by `inputs`.
+///
+/// A single hasher instance is reused for all inputs, which can be faster
+/// than calling [`Digest::digest`] for each input if hasher initialization
+/// is expensive.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn hash_many<D, I, B>(inputs: I) -> Vec<Output<D>>
+where
+    D: Digest + FixedOutputReset,
+    I: IntoIterator<Item = B>,
+    B: AsRef<[u8]>,
+{
+    let mut hasher = D::new();
+    inputs
+        .into_iter()
+        .map(|input| {
+            Digest::update(&mut hasher, input);
+            Digest::finalize_reset(&mut hasher)
+        })
+        .collect()
+}
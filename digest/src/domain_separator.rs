@@ -0,0 +1,54 @@
+use crate::{Digest, Output, Update};
+
+const FIELD: u8 = 0x01;
+const U64: u8 = 0x02;
+const TAG: u8 = 0x03;
+
+/// Builder for unambiguous hashing of structured data.
+///
+/// Every value is prefixed with a one-byte type marker. Byte fields and tags
+/// are additionally prefixed with their length encoded as a 64-bit
+/// little-endian integer (see [`Update::update_framed`]), while integers are
+/// encoded as fixed-width little-endian values. As a result two different
+/// sequences of values never produce the same hasher input.
+#[derive(Clone, Debug, Default)]
+pub struct DomainSeparator<D> {
+    hasher: D,
+}
+
+impl<D: Digest + Update> DomainSeparator<D> {
+    /// Create new builder.
+    pub fn new() -> Self {
+        Self { hasher: D::new() }
+    }
+
+    /// Process variable-length byte field.
+    #[inline]
+    pub fn field(mut self, data: &[u8]) -> Self {
+        Update::update(&mut self.hasher, &[FIELD]);
+        self.hasher.update_framed(data);
+        self
+    }
+
+    /// Process 64-bit integer.
+    #[inline]
+    pub fn u64(mut self, value: u64) -> Self {
+        Update::update(&mut self.hasher, &[U64]);
+        Update::update(&mut self.hasher, &value.to_le_bytes());
+        self
+    }
+
+    /// Process string tag, e.g. a protocol or message type name.
+    #[inline]
+    pub fn tag(mut self, tag: &str) -> Self {
+        Update::update(&mut self.hasher, &[TAG]);
+        self.hasher.update_framed(tag.as_bytes());
+        self
+    }
+
+    /// Retrieve result and consume the builder.
+    #[inline]
+    pub fn finalize(self) -> Output<D> {
+        self.hasher.finalize()
+    }
+}
@@ -1,4 +1,4 @@
-use super::{FixedOutput, FixedOutputReset, Reset, Update};
+use super::{FixedOutput, FixedOutputReset, Reset, Update, VariableOutput};
 use core::fmt;
 use generic_array::{typenum::Unsigned, GenericArray};
 
@@ -109,6 +109,107 @@ impl Clone for Box<dyn DynDigest> {
     }
 }
 
+/// The `DynVariableOutput` trait is a modification of `VariableOutput` trait
+/// suitable for trait objects.
+///
+/// Usually it is used for selecting both algorithm and output size at runtime.
+pub trait DynVariableOutput {
+    /// Digest input data.
+    ///
+    /// This method can be called repeatedly for use with streaming messages.
+    fn update(&mut self, data: &[u8]);
+
+    /// Write result into provided slice and consume boxed hasher instance.
+    ///
+    /// Returns error if buffer length is not equal to `output_size`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_variable_into_boxed(
+        self: Box<Self>,
+        out: &mut [u8],
+    ) -> Result<(), InvalidBufferLength>;
+
+    /// Retrieve result and consume boxed hasher instance.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_boxed(self: Box<Self>) -> Box<[u8]> {
+        let mut result = vec![0; self.output_size()];
+        self.finalize_variable_into_boxed(&mut result).unwrap();
+        result.into_boxed_slice()
+    }
+
+    /// Retrieve result and reset hasher instance.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_boxed_reset(&mut self) -> Box<[u8]> {
+        let mut result = vec![0; self.output_size()];
+        self.finalize_variable_reset_into(&mut result).unwrap();
+        result.into_boxed_slice()
+    }
+
+    /// Write result into provided slice and reset the hasher instance.
+    ///
+    /// Returns error if buffer length is not equal to `output_size`.
+    fn finalize_variable_reset_into(&mut self, out: &mut [u8]) -> Result<(), InvalidBufferLength>;
+
+    /// Reset hasher instance to its initial state.
+    fn reset(&mut self);
+
+    /// Get output size of the hasher instance.
+    fn output_size(&self) -> usize;
+
+    /// Get maximum output size of the hasher.
+    fn max_output_size(&self) -> usize;
+
+    /// Clone hasher state into a boxed trait object
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn box_clone(&self) -> Box<dyn DynVariableOutput>;
+}
+
+impl<D: Update + VariableOutput + Clone + 'static> DynVariableOutput for D {
+    fn update(&mut self, data: &[u8]) {
+        Update::update(self, data);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn finalize_variable_into_boxed(
+        self: Box<Self>,
+        out: &mut [u8],
+    ) -> Result<(), InvalidBufferLength> {
+        VariableOutput::finalize_variable_into(*self, out)
+    }
+
+    fn finalize_variable_reset_into(&mut self, out: &mut [u8]) -> Result<(), InvalidBufferLength> {
+        VariableOutput::finalize_variable_reset_into(self, out)
+    }
+
+    fn reset(&mut self) {
+        Reset::reset(self);
+    }
+
+    fn output_size(&self) -> usize {
+        VariableOutput::output_size(self)
+    }
+
+    fn max_output_size(&self) -> usize {
+        VariableOutput::max_output_size(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn box_clone(&self) -> Box<dyn DynVariableOutput> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl Clone for Box<dyn DynVariableOutput> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
 /// Buffer length is not equal to the hash output size.
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
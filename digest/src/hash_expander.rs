@@ -0,0 +1,88 @@
+use crate::{FixedOutput, Update, XofReader};
+use core::fmt;
+use generic_array::GenericArray;
+
+/// Extendable output reader built on a fixed-output hash function.
+///
+/// It implements the ANSI X9.63 key derivation function (also known as
+/// KDF2): output is a concatenation of blocks
+///
+/// `H(seed || counter || info)`
+///
+/// where `counter` is a 32-bit big-endian integer starting from 1 and
+/// incremented for every block, and `H` is the wrapped hash function. Hash
+/// state after processing of `seed` is computed once and cloned for every
+/// block.
+///
+/// The construction is deterministic, so it's suitable for deriving keys and
+/// other values from a high-entropy seed. It is not a replacement for a XOF
+/// when used with low-entropy inputs, e.g. passwords.
+///
+/// # Panics
+/// Reads panic after `(2^32 - 1) * D::OutputSize` bytes were produced,
+/// since the counter would overflow.
+#[derive(Clone)]
+pub struct HashExpander<'a, D: FixedOutput> {
+    prefix: D,
+    info: &'a [u8],
+    counter: u32,
+    block: GenericArray<u8, D::OutputSize>,
+    pos: usize,
+}
+
+impl<'a, D: FixedOutput + Update + Clone> HashExpander<'a, D> {
+    /// Create new expander using `hasher` and `seed` with empty `info`.
+    pub fn new(hasher: D, seed: &[u8]) -> Self {
+        Self::new_with_info(hasher, seed, &[])
+    }
+
+    /// Create new expander using `hasher`, `seed` and context `info`.
+    ///
+    /// State of `hasher` acts as a prefix of `seed`, so usually it should
+    /// be a fresh hasher instance.
+    pub fn new_with_info(mut hasher: D, seed: &[u8], info: &'a [u8]) -> Self {
+        hasher.update(seed);
+        let block = GenericArray::default();
+        let pos = block.len();
+        Self {
+            prefix: hasher,
+            info,
+            counter: 0,
+            block,
+            pos,
+        }
+    }
+
+    fn next_block(&mut self) {
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .expect("HashExpander counter overflow");
+        let mut hasher = self.prefix.clone();
+        hasher.update(&self.counter.to_be_bytes());
+        hasher.update(self.info);
+        hasher.finalize_into(&mut self.block);
+        self.pos = 0;
+    }
+}
+
+impl<'a, D: FixedOutput + Update + Clone> XofReader for HashExpander<'a, D> {
+    fn read(&mut self, mut buffer: &mut [u8]) {
+        while !buffer.is_empty() {
+            if self.pos == self.block.len() {
+                self.next_block();
+            }
+            let n = core::cmp::min(buffer.len(), self.block.len() - self.pos);
+            let (head, tail) = buffer.split_at_mut(n);
+            head.copy_from_slice(&self.block[self.pos..][..n]);
+            self.pos += n;
+            buffer = tail;
+        }
+    }
+}
+
+impl<'a, D: FixedOutput> fmt::Debug for HashExpander<'a, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HashExpander { .. }")
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{FixedOutput, Update};
+use generic_array::GenericArray;
+use std::io;
+
+/// Writer which passes written data to both the inner writer and a hasher.
+///
+/// Only bytes accepted by the inner writer are passed to the hasher, so
+/// the result is equal to hash of the written data.
+#[derive(Clone, Debug, Default)]
+pub struct TeeWriter<W, D> {
+    writer: W,
+    hasher: D,
+}
+
+impl<W: io::Write, D: Update> TeeWriter<W, D> {
+    /// Create new tee writer.
+    pub fn new(writer: W, hasher: D) -> Self {
+        Self { writer, hasher }
+    }
+
+    /// Finalize hasher and return it together with the inner writer.
+    pub fn finish(self) -> (W, GenericArray<u8, D::OutputSize>)
+    where
+        D: FixedOutput,
+    {
+        (self.writer, self.hasher.finalize_fixed())
+    }
+}
+
+impl<W: io::Write, D: Update> io::Write for TeeWriter<W, D> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.writer.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Reader which passes data read from the inner reader to a hasher.
+///
+/// Only bytes actually returned by the inner reader are passed to the hasher,
+/// so the result is equal to hash of the read data.
+#[derive(Clone, Debug, Default)]
+pub struct HashingReader<R, D> {
+    reader: R,
+    hasher: D,
+}
+
+impl<R: io::Read, D: Update> HashingReader<R, D> {
+    /// Create new hashing reader.
+    pub fn new(reader: R, hasher: D) -> Self {
+        Self { reader, hasher }
+    }
+
+    /// Finalize hasher and return it together with the inner reader.
+    pub fn finish(self) -> (R, GenericArray<u8, D::OutputSize>)
+    where
+        D: FixedOutput,
+    {
+        (self.reader, self.hasher.finalize_fixed())
+    }
+}
+
+impl<R: io::Read, D: Update> io::Read for HashingReader<R, D> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
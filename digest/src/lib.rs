@@ -5,8 +5,9 @@
 //! mid-level traits which expose more fine-grained functionality, and
 //! low-level traits intended to only be used by algorithm implementations:
 //!
-//! - **High-level convenience traits**: [`Digest`], [`DynDigest`]. They are wrappers
-//!   around lower-level traits for most common hash-function use-cases.
+//! - **High-level convenience traits**: [`Digest`], [`DynDigest`],
+//!   [`DynVariableOutput`]. They are wrappers around lower-level traits for
+//!   most common hash-function use-cases.
 //! - **Mid-level traits**: [`Update`], [`FixedOutput`], [`ExtendableOutput`], [`Reset`].
 //!   These traits atomically describe available functionality of hash function
 //!   implementations.
@@ -40,7 +41,15 @@ extern crate alloc;
 extern crate std;
 
 #[cfg(feature = "alloc")]
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
+
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+pub use rand_core;
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+pub use crypto_common::zeroize;
 
 #[cfg(feature = "dev")]
 #[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
@@ -49,18 +58,75 @@ pub mod dev;
 #[cfg(feature = "core-api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
 pub mod core_api;
+
+pub mod tree;
+
+mod counted;
+mod customized_xof;
 mod digest;
+mod domain_separator;
 mod dyn_digest;
+mod hash_expander;
+#[cfg(feature = "std")]
+mod io;
+mod limited;
+mod masked;
+mod multi_digest;
+mod ordered;
+mod prehashed;
+mod std_hasher;
+mod truncated;
+mod truncated_xof;
+mod xof_iter;
+#[cfg(feature = "rand_core")]
+mod xof_rng;
 
-pub use crate::digest::{Digest, Output};
-use core::fmt;
+#[cfg(feature = "subtle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "subtle")))]
+pub use crate::digest::ct_eq;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use crate::digest::hash_many;
+pub use crate::digest::{collect_digest, output_from_slice, Digest, Output};
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+pub use crate::digest::{to_array, ConstOutput};
+use core::{fmt, ops::RangeInclusive};
+pub use counted::Counted;
 #[cfg(feature = "core-api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
 pub use crypto_common::block_buffer;
-pub use dyn_digest::{DynDigest, InvalidBufferLength};
+pub use customized_xof::CustomizedXof;
+pub use domain_separator::DomainSeparator;
+pub use dyn_digest::{DynDigest, DynVariableOutput, InvalidBufferLength};
 pub use generic_array::{self, typenum::consts, GenericArray};
+pub use hash_expander::HashExpander;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use io::{HashingReader, TeeWriter};
+pub use limited::{LimitedUpdate, UpdateLimitExceeded};
+pub use masked::{EmptyMask, MaskedUpdate};
+pub use multi_digest::{MultiDigest2, MultiDigest3, MultiDigest4};
+pub use ordered::OrderedOutput;
+pub use prehashed::Prehashed;
+pub use std_hasher::{hash_into, StdHasher};
+pub use truncated::Truncated;
+pub use truncated_xof::{TruncatedXof, XofLimitBehavior, XofLimitExceeded};
+pub use xof_iter::XofBytes;
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+pub use xof_iter::XofChunks;
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+pub use xof_rng::XofRng;
+
+pub use crypto_common::{FixedOutput, FixedOutputReset, Reset, ResetFrom, ResetGuard, Update};
 
-pub use crypto_common::{FixedOutput, FixedOutputReset, Reset, Update};
+/// Marker trait for cryptographic hash functions.
+///
+/// It is not implemented by MACs and other keyed functions, which allows
+/// to restrict some helper methods to hash functions only.
+pub trait HashMarker {}
 
 /// Trait for describing readers which are used to extract extendable output
 /// from XOF (extendable-output function) result.
@@ -81,6 +147,79 @@ pub trait XofReader {
         self.read(&mut buf);
         buf
     }
+
+    /// Fill `dest` with output bytes.
+    ///
+    /// This is an alias of [`XofReader::read`] for code which uses XOF as
+    /// a deterministic byte generator.
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.read(dest);
+    }
+
+    /// Skip `n` bytes of output.
+    ///
+    /// The default implementation reads and discards output using a stack
+    /// buffer. Readers which implement [`XofReaderSeek`] should override it
+    /// with a more efficient implementation.
+    fn skip(&mut self, mut n: usize) {
+        let mut buf = [0u8; 64];
+        while n != 0 {
+            let len = core::cmp::min(n, buf.len());
+            self.read(&mut buf[..len]);
+            n -= len;
+        }
+    }
+
+    /// Create a snapshot of the reader.
+    ///
+    /// Readers are deterministic: the snapshot and the original reader
+    /// produce exactly the same continuation of the output stream, regardless
+    /// of how reads are split between calls. Reading from one of them does
+    /// not affect the other.
+    #[inline]
+    fn clone_reader(&self) -> Self
+    where
+        Self: Clone,
+    {
+        self.clone()
+    }
+
+    /// Convert reader into an infinite iterator over output bytes.
+    ///
+    /// The iterator never ends, so it must be bounded by the caller, e.g.
+    /// `reader.into_bytes().take(n)`.
+    fn into_bytes(self) -> XofBytes<Self>
+    where
+        Self: Sized,
+    {
+        XofBytes::new(self)
+    }
+
+    /// Convert reader into an infinite iterator over `N`-byte chunks of output.
+    ///
+    /// The iterator never ends, so it must be bounded by the caller, e.g.
+    /// `reader.chunks::<16>().take(n)`.
+    #[cfg(feature = "const-generics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+    fn chunks<const N: usize>(self) -> XofChunks<Self, N>
+    where
+        Self: Sized,
+    {
+        XofChunks::new(self)
+    }
+}
+
+/// Trait for XOF readers which can efficiently jump to an arbitrary
+/// position in the output stream, e.g. counter-mode based XOFs.
+pub trait XofReaderSeek: XofReader {
+    /// Get current position in the output stream in bytes.
+    fn position(&self) -> u64;
+
+    /// Move reader to the given position in the output stream in bytes.
+    ///
+    /// The following reads will return output starting from `pos`.
+    fn seek(&mut self, pos: u64);
 }
 
 /// Trait which describes extendable-output functions (XOF).
@@ -94,6 +233,26 @@ pub trait ExtendableOutput: Sized + Update + Default + Reset {
     /// Retrieve XOF reader and reset hasher instance state.
     fn finalize_xof_reset(&mut self) -> Self::Reader;
 
+    /// Write result into `out` and consume hasher instance.
+    ///
+    /// Exactly `out.len()` bytes are written. If `out` is empty, the hasher
+    /// is dropped without finalization. The default implementation reads
+    /// output using [`ExtendableOutput::finalize_xof`], implementations may
+    /// override it to write output directly.
+    fn finalize_xof_into(self, out: &mut [u8]) {
+        if !out.is_empty() {
+            self.finalize_xof().read(out);
+        }
+    }
+
+    /// Write result into `out` and reset hasher instance state.
+    ///
+    /// Exactly `out.len()` bytes are read. The hasher is reset even
+    /// if `out` is empty.
+    fn finalize_xof_reset_into(&mut self, out: &mut [u8]) {
+        self.finalize_xof_reset().read(out);
+    }
+
     /// Compute hash of `data` and write it to `output`.
     fn digest_xof(input: impl AsRef<[u8]>, output: &mut [u8]) {
         let mut hasher = Self::default();
@@ -133,15 +292,64 @@ pub trait VariableOutput: Sized + Update + Reset {
     /// Maximum size of output hash.
     const MAX_OUTPUT_SIZE: usize;
 
+    /// Minimum size of output hash.
+    const MIN_OUTPUT_SIZE: usize = 1;
+
     /// Create new hasher instance with the given output size.
     ///
-    /// It will return `Err(InvalidOutputSize)` in case if hasher can not return
-    /// hash of the specified output size.
+    /// It will return [`InvalidOutputSize`] with the requested and maximum
+    /// output sizes in case if hasher can not return hash of the specified
+    /// output size.
     fn new(output_size: usize) -> Result<Self, InvalidOutputSize>;
 
+    /// Create new hasher instance with output size providing `bits` of
+    /// collision resistance.
+    ///
+    /// Because of the birthday bound, output size is selected as twice
+    /// the requested security level, i.e. `ceil(2 * bits / 8)` bytes.
+    /// Users which need only preimage resistance can call
+    /// [`VariableOutput::new`] with `ceil(bits / 8)` bytes instead.
+    ///
+    /// Returns [`InvalidOutputSize`] if the selected output size is bigger
+    /// than [`VariableOutput::MAX_OUTPUT_SIZE`], i.e. if the algorithm can not
+    /// provide the requested security level.
+    fn new_for_security_bits(bits: usize) -> Result<Self, InvalidOutputSize> {
+        let output_size = bits.saturating_add(3) / 4;
+        if output_size > Self::MAX_OUTPUT_SIZE {
+            return Err(InvalidOutputSize::new(output_size, Self::MAX_OUTPUT_SIZE));
+        }
+        Self::new(output_size)
+    }
+
+    /// Create new hasher instance with the given output size, which must be
+    /// one of the `allowed` sizes.
+    ///
+    /// Returns [`NewInSetError::NotAllowed`] if `output_size` is not in
+    /// `allowed` and [`NewInSetError::Invalid`] if it's not supported by
+    /// the hasher.
+    fn new_in_set(output_size: usize, allowed: &[usize]) -> Result<Self, NewInSetError> {
+        if !allowed.contains(&output_size) {
+            return Err(NewInSetError::NotAllowed {
+                requested: output_size,
+            });
+        }
+        Self::new(output_size).map_err(NewInSetError::Invalid)
+    }
+
     /// Get output size of the hasher instance provided to the `new` method
     fn output_size(&self) -> usize;
 
+    /// Get maximum output size of the hasher, i.e. `Self::MAX_OUTPUT_SIZE`.
+    fn max_output_size(&self) -> usize {
+        Self::MAX_OUTPUT_SIZE
+    }
+
+    /// Get range of output sizes supported by the hasher, i.e.
+    /// `Self::MIN_OUTPUT_SIZE..=Self::MAX_OUTPUT_SIZE`.
+    fn output_size_range() -> RangeInclusive<usize> {
+        Self::MIN_OUTPUT_SIZE..=Self::MAX_OUTPUT_SIZE
+    }
+
     /// Retrieve result via closure and consume hasher.
     ///
     /// Closure is guaranteed to be called, length of the buffer passed to it
@@ -154,6 +362,30 @@ pub trait VariableOutput: Sized + Update + Reset {
     /// will be equal to `output_size`.
     fn finalize_variable_reset(&mut self, f: impl FnOnce(&[u8]));
 
+    /// Write result into `out` and consume hasher.
+    ///
+    /// Returns `Err(InvalidBufferLength)` if length of `out` is not equal
+    /// to `output_size`.
+    fn finalize_variable_into(self, out: &mut [u8]) -> Result<(), InvalidBufferLength> {
+        if out.len() != self.output_size() {
+            return Err(InvalidBufferLength);
+        }
+        self.finalize_variable(|res| out.copy_from_slice(res));
+        Ok(())
+    }
+
+    /// Write result into `out` and reset the hasher state.
+    ///
+    /// Returns `Err(InvalidBufferLength)` if length of `out` is not equal
+    /// to `output_size`. In this case the hasher state is left untouched.
+    fn finalize_variable_reset_into(&mut self, out: &mut [u8]) -> Result<(), InvalidBufferLength> {
+        if out.len() != self.output_size() {
+            return Err(InvalidBufferLength);
+        }
+        self.finalize_variable_reset(|res| out.copy_from_slice(res));
+        Ok(())
+    }
+
     /// Compute hash of `data` and write it to `output`.
     ///
     /// Length of the output hash is determined by `output`. If `output` is
@@ -169,6 +401,42 @@ pub trait VariableOutput: Sized + Update + Reset {
         Ok(())
     }
 
+    /// Retrieve result into a stack-allocated vector with capacity `N`
+    /// and consume hasher.
+    ///
+    /// Returns [`InvalidBufferLength`] without finalizing the hasher if
+    /// output size is bigger than `N`.
+    #[cfg(feature = "heapless")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+    fn finalize_variable_heapless<const N: usize>(
+        self,
+    ) -> Result<heapless::Vec<u8, N>, InvalidBufferLength> {
+        if self.output_size() > N {
+            return Err(InvalidBufferLength);
+        }
+        let mut buf = heapless::Vec::new();
+        self.finalize_variable(|res| {
+            buf.extend_from_slice(res)
+                .expect("output size is checked above")
+        });
+        Ok(buf)
+    }
+
+    /// Retrieve result into an array of length `N` and consume hasher.
+    ///
+    /// Returns [`InvalidBufferLength`] without finalizing the hasher if
+    /// output size is not equal to `N`.
+    #[cfg(feature = "const-generics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+    fn finalize_variable_array<const N: usize>(self) -> Result<[u8; N], InvalidBufferLength> {
+        if self.output_size() != N {
+            return Err(InvalidBufferLength);
+        }
+        let mut buf = [0u8; N];
+        self.finalize_variable(|res| buf.copy_from_slice(res));
+        Ok(buf)
+    }
+
     /// Retrieve result into a boxed slice and consume hasher.
     ///
     /// `Box<[u8]>` is used instead of `Vec<u8>` to save stack space, since
@@ -182,6 +450,15 @@ pub trait VariableOutput: Sized + Update + Reset {
         buf
     }
 
+    /// Retrieve result into a vector and consume hasher.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_variable_vec(self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.output_size());
+        self.finalize_variable(|res| buf.extend_from_slice(res));
+        buf
+    }
+
     /// Retrieve result into a boxed slice and reset hasher state.
     ///
     /// `Box<[u8]>` is used instead of `Vec<u8>` to save stack space, since
@@ -197,15 +474,87 @@ pub trait VariableOutput: Sized + Update + Reset {
 }
 
 /// The error type for variable hasher initialization.
-#[derive(Clone, Copy, Debug, Default)]
-pub struct InvalidOutputSize;
+///
+/// Contains the requested output size and the maximum output size
+/// supported by the hasher.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidOutputSize {
+    requested: usize,
+    max: usize,
+}
+
+impl InvalidOutputSize {
+    /// Create new error for the `requested` output size which is not
+    /// supported by a hasher with the maximum output size equal to `max`.
+    pub const fn new(requested: usize, max: usize) -> Self {
+        Self { requested, max }
+    }
+
+    /// Requested output size.
+    pub const fn requested(&self) -> usize {
+        self.requested
+    }
+
+    /// Maximum output size supported by the hasher.
+    pub const fn max(&self) -> usize {
+        self.max
+    }
+}
 
 impl fmt::Display for InvalidOutputSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("invalid output size")
+        write!(
+            f,
+            "invalid output size: requested {}, max {}",
+            self.requested, self.max
+        )
     }
 }
 
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std::error::Error for InvalidOutputSize {}
+
+/// The error type returned by [`VariableOutput::new_in_set`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NewInSetError {
+    /// Requested output size is not one of the allowed sizes.
+    NotAllowed {
+        /// Requested output size.
+        requested: usize,
+    },
+    /// Requested output size is not supported by the hasher.
+    Invalid(InvalidOutputSize),
+}
+
+impl fmt::Display for NewInSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewInSetError::NotAllowed { requested } => {
+                write!(f, "output size {} is not in the allowed set", requested)
+            }
+            NewInSetError::Invalid(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for NewInSetError {}
+
+/// The error type returned when computed digest is not equal to the expected value.
+#[cfg(feature = "subtle")]
+#[cfg_attr(docsrs, doc(cfg(feature = "subtle")))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DigestMismatch;
+
+#[cfg(feature = "subtle")]
+impl fmt::Display for DigestMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("digest mismatch")
+    }
+}
+
+#[cfg(all(feature = "subtle", feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for DigestMismatch {}
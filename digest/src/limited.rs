@@ -0,0 +1,137 @@
+use crate::{FixedOutput, FixedOutputReset, HashMarker, Reset, Update};
+use core::fmt;
+use generic_array::GenericArray;
+
+/// Wrapper which limits total number of bytes processed by the wrapped hasher.
+///
+/// It can be used to protect against unbounded hashing of untrusted input.
+/// Data is forwarded to the wrapped hasher until the byte budget is
+/// exceeded. Data which does not fit into the budget is not forwarded
+/// and the wrapper gets marked as overflowed, after which all subsequent
+/// updates are ignored. The budget and the overflow flag are restored
+/// together with the hasher state on reset.
+///
+/// The wrapper intentionally does not implement [`FixedOutput`], since a result
+/// computed over truncated input could be mistaken for the hash of the whole
+/// input. Use [`LimitedUpdate::try_finalize`] instead, which fails if the budget
+/// was exceeded.
+#[derive(Clone, Debug)]
+pub struct LimitedUpdate<D> {
+    inner: D,
+    limit: u64,
+    remaining: u64,
+    overflowed: bool,
+}
+
+impl<D> LimitedUpdate<D> {
+    /// Wrap `inner` hasher with budget of `limit` bytes.
+    pub fn new(inner: D, limit: u64) -> Self {
+        Self {
+            inner,
+            limit,
+            remaining: limit,
+            overflowed: false,
+        }
+    }
+
+    /// Get number of bytes which can be processed before the budget is exceeded.
+    #[inline]
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Check whether the byte budget was exceeded since creation or last reset.
+    #[inline]
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// Return the wrapped hasher.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: Update> LimitedUpdate<D> {
+    /// Process `data` if it fits into the remaining budget.
+    ///
+    /// Returns [`UpdateLimitExceeded`] without processing any data if `data`
+    /// does not fit into the budget or if the budget was already exceeded.
+    pub fn try_update(&mut self, data: &[u8]) -> Result<(), UpdateLimitExceeded> {
+        let len = data.len() as u64;
+        if self.overflowed || len > self.remaining {
+            self.overflowed = true;
+            return Err(UpdateLimitExceeded);
+        }
+        self.inner.update(data);
+        self.remaining -= len;
+        Ok(())
+    }
+}
+
+impl<D: FixedOutput> LimitedUpdate<D> {
+    /// Retrieve result and consume the wrapper.
+    ///
+    /// Returns [`UpdateLimitExceeded`] if the budget was exceeded, i.e. if
+    /// some data was not processed by the wrapped hasher.
+    pub fn try_finalize(self) -> Result<GenericArray<u8, D::OutputSize>, UpdateLimitExceeded> {
+        if self.overflowed {
+            return Err(UpdateLimitExceeded);
+        }
+        Ok(self.inner.finalize_fixed())
+    }
+}
+
+impl<D: FixedOutputReset> LimitedUpdate<D> {
+    /// Retrieve result and reset the wrapper.
+    ///
+    /// Returns [`UpdateLimitExceeded`] if the budget was exceeded, i.e. if
+    /// some data was not processed by the wrapped hasher. The wrapper gets
+    /// reset in both cases.
+    pub fn try_finalize_reset(
+        &mut self,
+    ) -> Result<GenericArray<u8, D::OutputSize>, UpdateLimitExceeded> {
+        let res = self.inner.finalize_fixed_reset();
+        let overflowed = self.overflowed;
+        self.remaining = self.limit;
+        self.overflowed = false;
+        if overflowed {
+            Err(UpdateLimitExceeded)
+        } else {
+            Ok(res)
+        }
+    }
+}
+
+impl<D: Update> Update for LimitedUpdate<D> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        // overflow is recorded in the flag
+        let _ = self.try_update(data);
+    }
+}
+
+impl<D: HashMarker> HashMarker for LimitedUpdate<D> {}
+
+impl<D: Reset> Reset for LimitedUpdate<D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.remaining = self.limit;
+        self.overflowed = false;
+    }
+}
+
+/// Attempt to process data past the byte budget of [`LimitedUpdate`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct UpdateLimitExceeded;
+
+impl fmt::Display for UpdateLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("update byte limit exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for UpdateLimitExceeded {}
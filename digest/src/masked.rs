@@ -0,0 +1,81 @@
+use crate::{FixedOutput, Reset, Update};
+use core::fmt;
+use generic_array::GenericArray;
+
+/// Wrapper which XORs processed data with a cyclically repeated mask
+/// before passing it to the wrapped hasher.
+///
+/// This is a data transformation helper for keyed constructions and test
+/// harnesses. It is NOT a cipher and does not provide any confidentiality.
+#[derive(Clone, Debug)]
+pub struct MaskedUpdate<'a, D> {
+    inner: D,
+    mask: &'a [u8],
+    pos: usize,
+}
+
+impl<'a, D> MaskedUpdate<'a, D> {
+    /// Wrap `inner` hasher using `mask`.
+    ///
+    /// Returns [`EmptyMask`] if `mask` is empty.
+    pub fn new(inner: D, mask: &'a [u8]) -> Result<Self, EmptyMask> {
+        if mask.is_empty() {
+            return Err(EmptyMask);
+        }
+        Ok(Self {
+            inner,
+            mask,
+            pos: 0,
+        })
+    }
+
+    /// Return the wrapped hasher.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<'a, D: Update> Update for MaskedUpdate<'a, D> {
+    fn update(&mut self, data: &[u8]) {
+        let mut buf = [0u8; 64];
+        for chunk in data.chunks(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+            for (b, &d) in buf.iter_mut().zip(chunk) {
+                *b = d ^ self.mask[self.pos];
+                self.pos = (self.pos + 1) % self.mask.len();
+            }
+            self.inner.update(buf);
+        }
+    }
+}
+
+impl<'a, D: Reset> Reset for MaskedUpdate<'a, D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.pos = 0;
+    }
+}
+
+impl<'a, D: FixedOutput> FixedOutput for MaskedUpdate<'a, D> {
+    type OutputSize = D::OutputSize;
+
+    #[inline]
+    fn finalize_into(self, out: &mut GenericArray<u8, Self::OutputSize>) {
+        self.inner.finalize_into(out);
+    }
+}
+
+/// Mask used for [`MaskedUpdate`] is empty.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct EmptyMask;
+
+impl fmt::Display for EmptyMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("mask is empty")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for EmptyMask {}
@@ -0,0 +1,61 @@
+use crate::{Digest, Output, Reset, Update};
+
+macro_rules! impl_multi_digest {
+    ($name:ident, $doc:literal, $($t:ident $f:ident),+) => {
+        #[doc = $doc]
+        ///
+        /// Every update is forwarded to all wrapped hashers, so input data
+        /// is traversed only once.
+        #[derive(Clone, Debug, Default)]
+        pub struct $name<$($t),+> {
+            $($f: $t,)+
+        }
+
+        impl<$($t: Digest + Update),+> $name<$($t),+> {
+            /// Create new wrapper with fresh hasher instances.
+            pub fn new() -> Self {
+                Self { $($f: $t::new(),)+ }
+            }
+
+            /// Wrap the given hasher instances.
+            pub fn from_hashers($($f: $t),+) -> Self {
+                Self { $($f,)+ }
+            }
+
+            /// Retrieve results of all hashers and consume the wrapper.
+            pub fn finalize(self) -> ($(Output<$t>,)+) {
+                ($(self.$f.finalize(),)+)
+            }
+        }
+
+        impl<$($t: Update),+> Update for $name<$($t),+> {
+            #[inline]
+            fn update(&mut self, data: &[u8]) {
+                $(Update::update(&mut self.$f, data);)+
+            }
+        }
+
+        impl<$($t: Reset),+> Reset for $name<$($t),+> {
+            #[inline]
+            fn reset(&mut self) {
+                $(self.$f.reset();)+
+            }
+        }
+    };
+}
+
+impl_multi_digest!(
+    MultiDigest2,
+    "Wrapper which computes two digests of the same data in one pass.",
+    A a, B b
+);
+impl_multi_digest!(
+    MultiDigest3,
+    "Wrapper which computes three digests of the same data in one pass.",
+    A a, B b, C c
+);
+impl_multi_digest!(
+    MultiDigest4,
+    "Wrapper which computes four digests of the same data in one pass.",
+    A a, B b, C c, D d
+);
@@ -0,0 +1,92 @@
+use crate::{Digest, Output};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// Wrapper around [`Output`] which provides total ordering of digests.
+///
+/// Digests are compared lexicographically by bytes, which makes it possible
+/// to use them as keys in `BTreeMap` or `HashMap` and to sort them in a stable
+/// manner, e.g. for content-addressed storage.
+///
+/// Comparison is NOT constant-time, so this type must not be used for
+/// verification of secret values. Use [`Digest::verify`] or [`ct_eq`][crate::ct_eq]
+/// instead.
+pub struct OrderedOutput<D: Digest>(Output<D>);
+
+impl<D: Digest> OrderedOutput<D> {
+    /// Wrap digest output.
+    #[inline]
+    pub fn new(output: Output<D>) -> Self {
+        Self(output)
+    }
+
+    /// Return the wrapped digest output.
+    #[inline]
+    pub fn into_inner(self) -> Output<D> {
+        self.0
+    }
+}
+
+impl<D: Digest> From<Output<D>> for OrderedOutput<D> {
+    #[inline]
+    fn from(output: Output<D>) -> Self {
+        Self(output)
+    }
+}
+
+impl<D: Digest> AsRef<[u8]> for OrderedOutput<D> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<D: Digest> Clone for OrderedOutput<D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<D: Digest> fmt::Debug for OrderedOutput<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OrderedOutput(")?;
+        for b in self.0.iter() {
+            write!(f, "{:02x}", b)?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<D: Digest> PartialEq for OrderedOutput<D> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..] == other.0[..]
+    }
+}
+
+impl<D: Digest> Eq for OrderedOutput<D> {}
+
+impl<D: Digest> PartialOrd for OrderedOutput<D> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D: Digest> Ord for OrderedOutput<D> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0[..].cmp(&other.0[..])
+    }
+}
+
+impl<D: Digest> Hash for OrderedOutput<D> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0[..].hash(state);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{FixedOutput, InvalidBufferLength, Reset, Update};
+use core::{fmt, marker::PhantomData};
+use generic_array::{typenum::Unsigned, GenericArray};
+
+/// Hasher which returns already computed digest of `D` fed into it.
+///
+/// It allows generic code, e.g. signature schemes with "prehash" mode,
+/// to accept either a message, which gets hashed using `D`, or a precomputed
+/// digest. Total length of the processed data must be exactly equal to
+/// `D::OutputSize`, otherwise [`Prehashed::try_finalize`] returns an error.
+///
+/// The wrapper intentionally does not implement [`FixedOutput`], since
+/// infallible finalization would have to panic on input of wrong length.
+pub struct Prehashed<D: FixedOutput> {
+    buf: GenericArray<u8, D::OutputSize>,
+    len: usize,
+    _hasher: PhantomData<D>,
+}
+
+impl<D: FixedOutput> Prehashed<D> {
+    /// Create wrapper from precomputed `digest`.
+    pub fn from_digest(digest: GenericArray<u8, D::OutputSize>) -> Self {
+        Self {
+            buf: digest,
+            len: D::OutputSize::USIZE,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Retrieve the precomputed digest and consume the wrapper.
+    ///
+    /// Returns [`InvalidBufferLength`] if length of the processed data is not
+    /// equal to `D::OutputSize`.
+    pub fn try_finalize(self) -> Result<GenericArray<u8, D::OutputSize>, InvalidBufferLength> {
+        if self.len == D::OutputSize::USIZE {
+            Ok(self.buf)
+        } else {
+            Err(InvalidBufferLength)
+        }
+    }
+}
+
+impl<D: FixedOutput> Clone for Prehashed<D> {
+    fn clone(&self) -> Self {
+        Self {
+            buf: self.buf.clone(),
+            len: self.len,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<D: FixedOutput> Default for Prehashed<D> {
+    fn default() -> Self {
+        Self {
+            buf: Default::default(),
+            len: 0,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<D: FixedOutput> fmt::Debug for Prehashed<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Prehashed { .. }")
+    }
+}
+
+impl<D: FixedOutput> Update for Prehashed<D> {
+    fn update(&mut self, data: &[u8]) {
+        let end = self.len.saturating_add(data.len());
+        if let Some(buf) = self.buf.get_mut(self.len..end) {
+            buf.copy_from_slice(data);
+            self.len = end;
+        } else {
+            // Mark input as invalid, so finalization always fails
+            self.len = D::OutputSize::USIZE + 1;
+        }
+    }
+}
+
+impl<D: FixedOutput> Reset for Prehashed<D> {
+    fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
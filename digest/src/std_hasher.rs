@@ -0,0 +1,83 @@
+use crate::{Digest, Update};
+use core::hash::{Hash, Hasher};
+
+/// Adapter which implements [`Hasher`] on top of a [`Digest`].
+///
+/// Data passed to [`Hasher::write`] is forwarded to the wrapped hasher.
+/// [`Hasher::finish`] does not consume or reset the hasher state, as required
+/// by the `Hasher` contract, instead it finalizes a clone of the wrapped
+/// hasher. The resulting value is the first 8 bytes of the digest
+/// interpreted as a little-endian integer, i.e. callers get only 64 bits
+/// of the cryptographic hash.
+///
+/// Since the adapter implements [`Default`], it can be used with
+/// [`BuildHasherDefault`][core::hash::BuildHasherDefault].
+#[derive(Clone, Default, Debug)]
+pub struct StdHasher<D> {
+    digest: D,
+}
+
+impl<D: Digest> StdHasher<D> {
+    /// Create new adapter with a fresh hasher.
+    #[inline]
+    pub fn new() -> Self {
+        Self { digest: D::new() }
+    }
+
+    /// Create new adapter around the given hasher.
+    #[inline]
+    pub fn from_digest(digest: D) -> Self {
+        Self { digest }
+    }
+
+    /// Return the wrapped hasher.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.digest
+    }
+}
+
+impl<D: Digest + Clone> Hasher for StdHasher<D> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        Digest::update(&mut self.digest, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let out = self.digest.clone().finalize();
+        let mut buf = [0u8; 8];
+        let n = core::cmp::min(buf.len(), out.len());
+        buf[..n].copy_from_slice(&out[..n]);
+        u64::from_le_bytes(buf)
+    }
+}
+
+/// Feed [`Hash`] representation of `value` into `digest`.
+///
+/// Bytes are produced by the [`Hash`] implementation of `value` and the
+/// default methods of [`Hasher`]. In particular, integers are written using
+/// native endianness, `usize`/`isize` values (e.g. slice lengths) have platform
+/// dependent size, and implementations of [`Hash`] are free to choose
+/// their own framing. Thus the resulting digest is stable only on one
+/// platform and is intended only for content-addressing within a single
+/// process. It must not be used for data exchanged across platforms or
+/// persisted between program versions.
+pub fn hash_into<H: Hash + ?Sized, D: Update>(value: &H, digest: &mut D) {
+    value.hash(&mut UpdateHasher(digest));
+}
+
+/// [`Hasher`] which forwards all written data to an [`Update`] implementation.
+struct UpdateHasher<'a, D: Update>(&'a mut D);
+
+impl<'a, D: Update> Hasher for UpdateHasher<'a, D> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        // never called by `hash_into`, the resulting digest is retrieved
+        // from the wrapped hasher instead
+        0
+    }
+}
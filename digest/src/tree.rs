@@ -0,0 +1,60 @@
+//! Two-level tree hashing construction.
+//!
+//! **NOTE:** result of [`tree_hash`] is NOT equal to result of the native
+//! hash function over the same data. It is a separate construction built on
+//! top of a [`Digest`] implementation and should be used only by protocols
+//! which explicitly specify it.
+//!
+//! The construction works as follows:
+//!
+//! 1. Data is split into chunks of `leaf_size` bytes, the last chunk can
+//!    be shorter.
+//! 2. Each chunk is hashed as a leaf: `leaf_i = H(0x00 || chunk_i)`.
+//! 3. The root is computed as `H(0x01 || LE64(leaf_size) || leaf_1 || ... || leaf_n)`,
+//!    where `LE64` is a 64-bit little-endian encoding.
+//!
+//! For empty data the number of leaves is zero. If the `parallel` feature
+//! is enabled, leaves are hashed in parallel.
+
+use crate::{Digest, Output};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Compute tree hash of `data` using `leaf_size` chunks.
+///
+/// # Panics
+/// If `leaf_size` is equal to zero.
+pub fn tree_hash<D: Digest>(data: &[u8], leaf_size: usize) -> Output<D> {
+    assert!(leaf_size != 0, "leaf size must not be zero");
+    let mut root = D::new();
+    root.update([NODE_PREFIX]);
+    root.update((leaf_size as u64).to_le_bytes());
+    hash_leaves::<D>(&mut root, data, leaf_size);
+    root.finalize()
+}
+
+fn hash_leaf<D: Digest>(chunk: &[u8]) -> Output<D> {
+    D::new()
+        .chain_update([LEAF_PREFIX])
+        .chain_update(chunk)
+        .finalize()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn hash_leaves<D: Digest>(root: &mut D, data: &[u8], leaf_size: usize) {
+    for chunk in data.chunks(leaf_size) {
+        root.update(hash_leaf::<D>(chunk));
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn hash_leaves<D: Digest>(root: &mut D, data: &[u8], leaf_size: usize) {
+    use alloc::vec::Vec;
+    use rayon::prelude::*;
+
+    let leaves: Vec<Output<D>> = data.par_chunks(leaf_size).map(hash_leaf::<D>).collect();
+    for leaf in leaves {
+        root.update(leaf);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    FixedOutput, FixedOutputReset, HashMarker, InvalidOutputSize, Reset, Update, VariableOutput,
+};
+use generic_array::typenum::Unsigned;
+
+/// Wrapper which provides [`VariableOutput`] interface over a fixed-output
+/// hash function by truncating its result.
+///
+/// Output size can be in the range from 1 to `D::OutputSize`. Finalization
+/// computes the full digest and returns its prefix of the requested length,
+/// e.g. it can be used for "SHA-256 truncated to 16 bytes". Note that
+/// truncated output is not equal to output of dedicated truncated variants
+/// of hash functions (e.g. SHA-512/256), which use different initial state.
+#[derive(Clone, Debug)]
+pub struct Truncated<D> {
+    hasher: D,
+    output_size: usize,
+}
+
+impl<D: FixedOutput> Truncated<D> {
+    /// Wrap `hasher` with the given truncated output size.
+    ///
+    /// Returns [`InvalidOutputSize`] if `output_size` is equal to zero
+    /// or bigger than `D::OutputSize`.
+    pub fn from_fixed(hasher: D, output_size: usize) -> Result<Self, InvalidOutputSize> {
+        if output_size == 0 || output_size > D::OutputSize::USIZE {
+            return Err(InvalidOutputSize::new(output_size, D::OutputSize::USIZE));
+        }
+        Ok(Self {
+            hasher,
+            output_size,
+        })
+    }
+
+    /// Return the wrapped hasher.
+    pub fn into_inner(self) -> D {
+        self.hasher
+    }
+}
+
+impl<D: Update> Update for Truncated<D> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+}
+
+impl<D: HashMarker> HashMarker for Truncated<D> {}
+
+impl<D: Reset> Reset for Truncated<D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.hasher.reset();
+    }
+}
+
+impl<D: Update + FixedOutputReset + Default> VariableOutput for Truncated<D> {
+    const MAX_OUTPUT_SIZE: usize = D::OutputSize::USIZE;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        Self::from_fixed(D::default(), output_size)
+    }
+
+    fn output_size(&self) -> usize {
+        self.output_size
+    }
+
+    fn finalize_variable(self, f: impl FnOnce(&[u8])) {
+        let res = self.hasher.finalize_fixed();
+        f(&res[..self.output_size]);
+    }
+
+    fn finalize_variable_reset(&mut self, f: impl FnOnce(&[u8])) {
+        let res = self.hasher.finalize_fixed_reset();
+        f(&res[..self.output_size]);
+    }
+}
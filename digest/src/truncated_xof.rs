@@ -0,0 +1,104 @@
+use crate::XofReader;
+use core::fmt;
+
+/// Behavior of [`TruncatedXof`] on attempts to read past its limit.
+///
+/// The default and recommended behavior is [`XofLimitBehavior::Panic`], since
+/// zero-filled output can be silently used in place of real key material.
+/// Use [`XofLimitBehavior::ZeroFill`] only if a protocol explicitly defines
+/// output past the limit as zeros.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum XofLimitBehavior {
+    /// Fill bytes past the limit with zeros.
+    ZeroFill,
+    /// Panic without reading any data.
+    Panic,
+}
+
+impl Default for XofLimitBehavior {
+    #[inline]
+    fn default() -> Self {
+        XofLimitBehavior::Panic
+    }
+}
+
+/// Wrapper around [`XofReader`] which limits total length of its output.
+///
+/// Reads are passed to the wrapped reader until the limit is reached.
+/// Reads which cross the limit are handled according to
+/// [`XofLimitBehavior`]. For fallible reads use [`TruncatedXof::try_read`],
+/// which never panics and does not depend on the selected behavior.
+#[derive(Clone, Debug)]
+pub struct TruncatedXof<R> {
+    reader: R,
+    remaining: usize,
+    behavior: XofLimitBehavior,
+}
+
+impl<R: XofReader> TruncatedXof<R> {
+    /// Wrap `reader`, limiting its output to `limit` bytes.
+    pub fn new(reader: R, limit: usize, behavior: XofLimitBehavior) -> Self {
+        Self {
+            reader,
+            remaining: limit,
+            behavior,
+        }
+    }
+
+    /// Get number of bytes which can be read before reaching the limit.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Read output into the `buffer`.
+    ///
+    /// Returns [`XofLimitExceeded`] without reading any data if `buffer`
+    /// is longer than the number of remaining bytes.
+    pub fn try_read(&mut self, buffer: &mut [u8]) -> Result<(), XofLimitExceeded> {
+        if buffer.len() > self.remaining {
+            return Err(XofLimitExceeded);
+        }
+        self.reader.read(buffer);
+        self.remaining -= buffer.len();
+        Ok(())
+    }
+
+    /// Return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: XofReader> XofReader for TruncatedXof<R> {
+    fn read(&mut self, buffer: &mut [u8]) {
+        if self.try_read(buffer).is_ok() {
+            return;
+        }
+        match self.behavior {
+            XofLimitBehavior::ZeroFill => {
+                let (head, tail) = buffer.split_at_mut(self.remaining);
+                self.reader.read(head);
+                self.remaining = 0;
+                for b in tail.iter_mut() {
+                    *b = 0;
+                }
+            }
+            XofLimitBehavior::Panic => panic!("{}", XofLimitExceeded),
+        }
+    }
+}
+
+/// Attempt to read XOF output past the limit of [`TruncatedXof`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct XofLimitExceeded;
+
+impl fmt::Display for XofLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("XOF output limit exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for XofLimitExceeded {}
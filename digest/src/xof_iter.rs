@@ -0,0 +1,86 @@
+use crate::XofReader;
+
+/// Size of the internal buffer used by iterator adapters.
+const BUF_SIZE: usize = 32;
+
+/// Infinite iterator over bytes produced by [`XofReader`].
+///
+/// Created by the [`XofReader::into_bytes`] method. Data is read from the
+/// wrapped reader in small chunks to avoid a `read` call per byte.
+///
+/// The iterator never returns `None`, so it must be bounded by the caller,
+/// e.g. using [`Iterator::take`].
+#[derive(Clone, Debug)]
+pub struct XofBytes<R: XofReader> {
+    reader: R,
+    buf: [u8; BUF_SIZE],
+    pos: usize,
+}
+
+impl<R: XofReader> XofBytes<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: [0; BUF_SIZE],
+            pos: BUF_SIZE,
+        }
+    }
+}
+
+impl<R: XofReader> Iterator for XofBytes<R> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.pos == BUF_SIZE {
+            self.reader.read(&mut self.buf);
+            self.pos = 0;
+        }
+        let b = self.buf[self.pos];
+        self.pos += 1;
+        Some(b)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Infinite iterator over fixed-size chunks produced by [`XofReader`].
+///
+/// Created by the [`XofReader::chunks`] method. Every call to `next` reads
+/// `N` bytes from the wrapped reader into a stack array.
+///
+/// The iterator never returns `None`, so it must be bounded by the caller,
+/// e.g. using [`Iterator::take`].
+#[cfg(feature = "const-generics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "const-generics")))]
+#[derive(Clone, Debug)]
+pub struct XofChunks<R: XofReader, const N: usize> {
+    reader: R,
+}
+
+#[cfg(feature = "const-generics")]
+impl<R: XofReader, const N: usize> XofChunks<R, N> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+#[cfg(feature = "const-generics")]
+impl<R: XofReader, const N: usize> Iterator for XofChunks<R, N> {
+    type Item = [u8; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<[u8; N]> {
+        let mut chunk = [0u8; N];
+        self.reader.read(&mut chunk);
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::XofReader;
+use rand_core::{Error, RngCore};
+
+/// Deterministic random number generator driven by XOF output.
+///
+/// The generated stream is completely determined by the XOF input,
+/// e.g. it can be used for reproducible sampling in protocol simulations.
+/// It is NOT a replacement for a system RNG and should not be used for
+/// generation of secret values unless the XOF input is secret and is used
+/// only once.
+#[derive(Clone, Debug)]
+pub struct XofRng<R> {
+    reader: R,
+}
+
+impl<R: XofReader> XofRng<R> {
+    /// Create new generator from XOF `reader`.
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: XofReader> RngCore for XofRng<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.reader.read(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.reader.read(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.reader.read(dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.reader.read(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "dev", feature = "core-api"))]
+
+mod mock;
+
+use digest::{
+    consts::U32,
+    core_api::{CoreWrapper, CtVariableCoreWrapper, RtVariableCoreWrapper},
+    dev,
+};
+use mock::MockCore;
+
+type MockHash = CoreWrapper<CtVariableCoreWrapper<MockCore, U32>>;
+type MockVar = RtVariableCoreWrapper<MockCore>;
+
+const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+#[test]
+fn reset_equivalence() {
+    dev::reset_equivalence::<MockHash>(MSG, b"");
+    dev::reset_equivalence::<MockHash>(&MSG[..5], &MSG[5..]);
+}
+
+#[test]
+fn variable_reset_equivalence() {
+    for &output_size in &[1, 24, 64] {
+        dev::variable_reset_equivalence::<MockVar>(MSG, &MSG[7..], output_size);
+    }
+}
+
+#[test]
+fn update_chunking_invariant() {
+    use digest::Counted;
+
+    let data: Vec<u8> = (0u8..=80).collect();
+    for n in 0..data.len() {
+        dev::update_chunking_invariant::<MockHash>(&data[..n]);
+        dev::update_chunking_invariant::<Counted<MockHash>>(&data[..n]);
+    }
+}
@@ -0,0 +1,1027 @@
+#![cfg(feature = "core-api")]
+
+mod mock;
+
+use digest::{
+    consts::U32,
+    core_api::{CoreWrapper, CtVariableCoreWrapper, UpdateCore},
+    Digest,
+};
+use mock::MockCore;
+
+type MockHash = CoreWrapper<CtVariableCoreWrapper<MockCore, U32>>;
+
+const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+#[cfg(feature = "subtle")]
+#[test]
+fn verify() {
+    let expected = MockHash::digest(MSG);
+    assert!(MockHash::new().chain_update(MSG).verify(&expected).is_ok());
+
+    let mut hasher = MockHash::new();
+    hasher.update(&MSG[1..]);
+    assert!(hasher.verify_reset(&expected).is_err());
+    hasher.update(MSG);
+    assert!(hasher.verify_reset(&expected).is_ok());
+}
+
+#[test]
+fn checkpoint_restore() {
+    let (prefix, a, b) = (&MSG[..20], &MSG[20..30], &MSG[30..]);
+
+    let mut hasher = MockHash::new().chain_update(prefix);
+    let checkpoint = hasher.checkpoint();
+    hasher.update(a);
+    let res_a = hasher.finalize_reset();
+    hasher.restore(&checkpoint);
+    hasher.update(b);
+    let res_b = hasher.finalize();
+
+    let expected_a = MockHash::new().chain_update(prefix).chain_update(a);
+    let expected_b = MockHash::new().chain_update(prefix).chain_update(b);
+    assert_eq!(res_a, expected_a.finalize());
+    assert_eq!(res_b, expected_b.finalize());
+}
+
+#[cfg(feature = "const-generics")]
+#[test]
+fn const_generic_output_size() {
+    use digest::core_api::CtVariableCore;
+
+    type ConstMockHash = CoreWrapper<CtVariableCore<MockCore, 32>>;
+    assert_eq!(ConstMockHash::digest(MSG), MockHash::digest(MSG));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn update_from_reader() {
+    use digest::Update;
+
+    let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    let mut hasher = MockHash::new();
+    let n = hasher.update_from_reader(&mut &data[..]).unwrap();
+    assert_eq!(n, data.len() as u64);
+    assert_eq!(hasher.finalize(), MockHash::digest(&data));
+}
+
+#[cfg(feature = "hex")]
+#[test]
+fn finalize_hex() {
+    let hex = format!("{:x}", MockHash::digest(MSG));
+    let hasher = MockHash::new().chain_update(MSG);
+    assert_eq!(hasher.clone().finalize_hex(), hex);
+    assert_eq!(hasher.finalize_hex_upper(), hex.to_uppercase());
+}
+
+#[test]
+fn chain_iter() {
+    use digest::Update;
+
+    let parts = [&MSG[..3], &MSG[3..3], &MSG[3..17], &MSG[17..]];
+    let mut expected = MockHash::new();
+    for part in parts.iter() {
+        Update::update(&mut expected, part);
+    }
+    let res = MockHash::new().chain_iter(parts.iter());
+    assert_eq!(res.finalize(), expected.finalize());
+}
+
+#[test]
+fn truncated() {
+    use digest::{Truncated, VariableOutput};
+
+    let full = MockHash::digest(MSG);
+    let mut out = [0u8; 16];
+    Truncated::<MockHash>::digest_variable(MSG, &mut out).unwrap();
+    assert_eq!(out[..], full[..16]);
+    assert!(Truncated::<MockHash>::new(0).is_err());
+
+    let err = Truncated::<MockHash>::new(33).unwrap_err();
+    assert_eq!((err.requested(), err.max()), (33, 32));
+    assert_eq!(err.to_string(), "invalid output size: requested 33, max 32");
+}
+
+#[test]
+fn tree_hash() {
+    use digest::tree::tree_hash;
+
+    let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+    let mut root = MockHash::new()
+        .chain_update([1])
+        .chain_update(100u64.to_le_bytes());
+    for chunk in data.chunks(100) {
+        root.update(
+            MockHash::new()
+                .chain_update([0])
+                .chain_update(chunk)
+                .finalize(),
+        );
+    }
+    assert_eq!(tree_hash::<MockHash>(&data, 100), root.finalize());
+    assert_ne!(
+        tree_hash::<MockHash>(&data, 100),
+        tree_hash::<MockHash>(&data, 10)
+    );
+}
+
+#[cfg(feature = "const-generics")]
+#[test]
+fn multi_hasher() {
+    use digest::{
+        consts::U16,
+        core_api::{FixedOutputCore, MultiHasher, UpdateCore, UpdateMany},
+        generic_array::GenericArray,
+    };
+
+    #[derive(Default)]
+    struct LaneCore(CtVariableCoreWrapper<MockCore, U32>);
+
+    impl UpdateCore for LaneCore {
+        type BlockSize = U16;
+        type Buffer = <MockCore as UpdateCore>::Buffer;
+
+        fn update_blocks(&mut self, blocks: &[GenericArray<u8, U16>]) {
+            self.0.update_blocks(blocks)
+        }
+    }
+
+    impl FixedOutputCore for LaneCore {
+        type OutputSize = U32;
+
+        fn finalize_fixed_core(
+            &mut self,
+            buffer: &mut Self::Buffer,
+            out: &mut GenericArray<u8, U32>,
+        ) {
+            self.0.finalize_fixed_core(buffer, out)
+        }
+    }
+
+    impl UpdateMany<3> for LaneCore {}
+
+    let inputs = [&MSG[..5], &MSG[5..], &[][..]];
+    let mut hasher = MultiHasher::<LaneCore, 3>::default();
+    hasher.update([&inputs[0][..2], &inputs[1][..17], inputs[2]]);
+    hasher.update([&inputs[0][2..], &inputs[1][17..], inputs[2]]);
+    let res = hasher.finalize();
+    for (input, res) in inputs.iter().zip(res.iter()) {
+        assert_eq!(&MockHash::digest(input), res);
+    }
+}
+
+#[test]
+fn ct_variable_eq() {
+    type Core = CtVariableCoreWrapper<MockCore, U32>;
+
+    let (mut a, mut b) = (Core::default(), Core::default());
+    assert!(a == b);
+    a.update_blocks(&[Default::default()]);
+    assert!(a != b);
+    b.update_blocks(&[Default::default()]);
+    assert!(a == b);
+}
+
+#[test]
+fn ct_variable_debug() {
+    let core = CtVariableCoreWrapper::<MockCore, U32>::default();
+    assert_eq!(format!("{:?}", core), "Mock_32 { .. }");
+}
+
+#[test]
+fn ct_variable_try_new() {
+    use digest::consts::U24;
+    use mock::Pow2Core;
+
+    assert!(CtVariableCoreWrapper::<Pow2Core, U32>::try_new().is_ok());
+
+    let res = CtVariableCoreWrapper::<Pow2Core, U24>::try_new();
+    let err = res.err().unwrap();
+    assert_eq!((err.requested(), err.max()), (24, 64));
+}
+
+#[test]
+fn digest_iter() {
+    let chunks = [&MSG[..4], &MSG[4..4], &MSG[4..19], &MSG[19..]];
+    assert_eq!(MockHash::digest_iter(chunks.iter()), MockHash::digest(MSG));
+    assert_eq!(
+        MockHash::digest_iter(core::iter::empty::<&[u8]>()),
+        MockHash::digest(b"")
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn std_hasher() {
+    use core::hash::{BuildHasherDefault, Hasher};
+    use digest::StdHasher;
+    use std::collections::HashSet;
+
+    let expected = MockHash::digest(MSG);
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&expected[..8]);
+    let expected = u64::from_le_bytes(buf);
+
+    let mut hasher = StdHasher::<MockHash>::new();
+    hasher.write(&MSG[..10]);
+    let prefix = hasher.finish();
+    // `finish` must not consume or reset the state
+    assert_eq!(hasher.finish(), prefix);
+    hasher.write(&MSG[10..]);
+    assert_eq!(hasher.finish(), expected);
+    assert_eq!(hasher.into_inner().finalize(), MockHash::digest(MSG));
+
+    let mut set = HashSet::<&str, BuildHasherDefault<StdHasher<MockHash>>>::default();
+    set.insert("foo");
+    assert!(set.contains("foo"));
+    assert!(!set.contains("bar"));
+}
+
+#[test]
+fn hash_into() {
+    use core::hash::Hash;
+    use digest::StdHasher;
+
+    let value = (42u32, "foo", [1u8, 2, 3]);
+    let mut hasher = MockHash::new();
+    digest::hash_into(&value, &mut hasher);
+
+    let mut expected = StdHasher::<MockHash>::new();
+    value.hash(&mut expected);
+    let res = hasher.finalize();
+    assert_eq!(res, expected.into_inner().finalize());
+
+    let mut other = MockHash::new();
+    digest::hash_into(&(43u32, "foo", [1u8, 2, 3]), &mut other);
+    assert_ne!(other.finalize(), res);
+}
+
+#[test]
+fn finalize_reset() {
+    let mut hasher = MockHash::new();
+    for msg in MSG.chunks(7) {
+        hasher.update(msg);
+        assert_eq!(hasher.finalize_reset(), MockHash::digest(msg));
+    }
+}
+
+#[test]
+fn xof_skip() {
+    use digest::{XofReader, XofReaderSeek};
+    use mock::CounterReader;
+
+    let mut reader = CounterReader::default();
+    reader.skip(1000);
+    assert_eq!(reader.position(), 1000);
+
+    let (mut a, mut b) = ([0u8; 16], [0u8; 16]);
+    reader.read(&mut a);
+    let mut reader = CounterReader::default();
+    reader.seek(1000);
+    reader.read(&mut b);
+    assert_eq!(a, b);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn xof_reader_io_reader() {
+    use digest::{core_api::XofReaderIoReader, ExtendableOutput, Update, XofReader};
+    use mock::MockXofCore;
+    use std::io::Read;
+
+    let mut hasher = CoreWrapper::<MockXofCore>::default();
+    hasher.update(MSG);
+    let mut reader = hasher.finalize_xof();
+    let mut io_reader = XofReaderIoReader::new(reader.clone_reader());
+
+    let (mut a, mut b) = ([0u8; 100], [0u8; 100]);
+    XofReader::read(&mut reader, &mut a[..30]);
+    XofReader::read(&mut reader, &mut a[30..]);
+    io_reader.read_exact(&mut b[..30]).unwrap();
+    io_reader.read_exact(&mut b[30..]).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn clone_reader() {
+    use digest::{ExtendableOutput, Update, XofReader};
+    use mock::MockXofCore;
+
+    let mut hasher = CoreWrapper::<MockXofCore>::default();
+    hasher.update(MSG);
+    let mut reader = hasher.finalize_xof();
+    let mut prefix = [0u8; 5];
+    reader.read(&mut prefix);
+
+    let mut snapshot = reader.clone_reader();
+    let (mut a, mut b) = ([0u8; 40], [0u8; 40]);
+    reader.read(&mut a);
+    snapshot.read(&mut b[..7]);
+    snapshot.read(&mut b[7..]);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn read_blocks() {
+    use digest::{consts::U16, generic_array::GenericArray, ExtendableOutput, XofReader};
+    use mock::MockXofCore;
+
+    let reader = CoreWrapper::<MockXofCore>::default().finalize_xof();
+    let mut expected = [0u8; 51];
+    reader.clone().read(&mut expected);
+
+    // aligned reads are passed to the core
+    let mut aligned = reader.clone();
+    let mut blocks = [GenericArray::<u8, U16>::default(); 3];
+    aligned.read_blocks(&mut blocks);
+    assert!(blocks.iter().flatten().eq(expected[..48].iter()));
+
+    // unaligned reads go through the buffer
+    let mut unaligned = reader;
+    let mut head = [0u8; 3];
+    unaligned.read(&mut head);
+    unaligned.read_blocks(&mut blocks);
+    assert_eq!(head, expected[..3]);
+    assert!(blocks.iter().flatten().eq(expected[3..].iter()));
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn update_buf() {
+    use bytes::Buf;
+    use digest::Update;
+
+    let mut buf = (&MSG[..10]).chain(&MSG[10..25]).chain(&MSG[25..]);
+    let mut hasher = MockHash::new();
+    hasher.update_buf(&mut buf);
+    assert!(!buf.has_remaining());
+    assert_eq!(hasher.finalize(), MockHash::digest(MSG));
+}
+
+#[test]
+fn finalize_xof_into() {
+    use digest::{ExtendableOutput, Update};
+    use mock::MockXofCore;
+
+    let mut expected = [0u8; 40];
+    CoreWrapper::<MockXofCore>::digest_xof(MSG, &mut expected);
+
+    let mut hasher = CoreWrapper::<MockXofCore>::default();
+    hasher.update(MSG);
+    hasher.clone().finalize_xof_into(&mut []);
+    let mut out = [0u8; 40];
+    hasher.finalize_xof_into(&mut out);
+    assert_eq!(out, expected);
+}
+
+#[cfg(feature = "const-generics")]
+#[test]
+fn xof_chunks() {
+    use digest::XofReader;
+    use mock::CounterReader;
+
+    let mut expected = [0u8; 48];
+    CounterReader::default().read(&mut expected);
+    let chunks: Vec<[u8; 16]> = CounterReader::default().chunks().take(3).collect();
+    assert!(chunks.iter().flatten().eq(expected.iter()));
+}
+
+#[test]
+fn hash_expander() {
+    use digest::{HashExpander, Update, XofReader};
+
+    let (seed, info) = (b"seed", b"info");
+    let mut expected = Vec::new();
+    for counter in 1u32..=3 {
+        let mut hasher = MockHash::new();
+        Update::update(&mut hasher, seed);
+        hasher.update_u32_be(counter);
+        Update::update(&mut hasher, info);
+        expected.extend_from_slice(&hasher.finalize());
+    }
+
+    let mut reader = HashExpander::new_with_info(MockHash::new(), seed, info);
+    let mut buf = [0u8; 96];
+    reader.read(&mut buf[..5]);
+    reader.read(&mut buf[5..40]);
+    reader.read(&mut buf[40..]);
+    assert_eq!(buf[..], expected[..]);
+}
+
+#[test]
+fn new_with_domain() {
+    let domain = b"example.com v1";
+    let expected = MockHash::new()
+        .chain_update((domain.len() as u64).to_le_bytes())
+        .chain_update(domain)
+        .chain_update(MSG)
+        .finalize();
+    let res = MockHash::new_with_domain(domain)
+        .chain_update(MSG)
+        .finalize();
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn size_getters() {
+    use digest::DynDigest;
+
+    let hasher = MockHash::new();
+    assert_eq!(hasher.block_size(), 16);
+    assert_eq!(
+        DynDigest::output_size(&hasher),
+        <MockHash as Digest>::output_size()
+    );
+}
+
+#[test]
+fn counted() {
+    use digest::Counted;
+
+    let mut hasher = Counted::<MockHash>::default();
+    Digest::update(&mut hasher, &MSG[..10]);
+    Digest::update(&mut hasher, &MSG[10..]);
+    assert_eq!(hasher.count(), MSG.len() as u64);
+    assert_eq!(hasher.finalize_reset(), MockHash::digest(MSG));
+    assert_eq!(hasher.count(), 0);
+
+    let (res, count) = hasher.chain_update(MSG).finalize_with_count();
+    assert_eq!((res, count), (MockHash::digest(MSG), MSG.len() as u64));
+}
+
+#[test]
+fn limited_update() {
+    use digest::{LimitedUpdate, Update, UpdateLimitExceeded};
+
+    let mut hasher = LimitedUpdate::new(MockHash::new(), MSG.len() as u64);
+    assert!(hasher.try_update(&MSG[..10]).is_ok());
+    Update::update(&mut hasher, &MSG[10..]);
+    assert_eq!((hasher.remaining(), hasher.overflowed()), (0, false));
+    assert!(hasher.try_update(b"x").is_err());
+    assert!(hasher.overflowed());
+    assert_eq!(hasher.try_finalize_reset(), Err(UpdateLimitExceeded));
+    assert!(!hasher.overflowed());
+
+    Update::update(&mut hasher, MSG);
+    assert_eq!(hasher.try_finalize_reset(), Ok(MockHash::digest(MSG)));
+    assert_eq!(hasher.remaining(), MSG.len() as u64);
+
+    Update::update(&mut hasher, &MSG[..10]);
+    Update::update(&mut hasher, MSG);
+    Update::update(&mut hasher, b"x");
+    assert!(hasher.overflowed());
+    assert_eq!(hasher.remaining(), MSG.len() as u64 - 10);
+    assert_eq!(hasher.clone().try_finalize(), Err(UpdateLimitExceeded));
+
+    let mut hasher = LimitedUpdate::new(MockHash::new(), MSG.len() as u64);
+    Update::update(&mut hasher, MSG);
+    assert_eq!(hasher.try_finalize(), Ok(MockHash::digest(MSG)));
+}
+
+#[test]
+fn size_consts() {
+    const OUT: usize = <MockHash as Digest>::OUTPUT_SIZE;
+    const BLOCK: usize = MockHash::BLOCK_SIZE;
+
+    let mut buf = [0u8; OUT];
+    MockHash::new().finalize_into_slice(&mut buf).unwrap();
+    assert_eq!(buf[..], MockHash::digest(b"")[..]);
+    assert_eq!([0u8; BLOCK].len(), MockHash::new().block_size());
+}
+
+#[test]
+fn truncated_xof() {
+    use digest::{TruncatedXof, XofLimitBehavior, XofReader};
+    use mock::CounterReader;
+
+    let mut expected = [0u8; 40];
+    CounterReader::default().read(&mut expected[..32]);
+
+    let mut reader = TruncatedXof::new(CounterReader::default(), 32, XofLimitBehavior::ZeroFill);
+    let mut buf = [0xFFu8; 40];
+    reader.read(&mut buf[..10]);
+    assert!(reader.try_read(&mut buf[10..]).is_err());
+    reader.read(&mut buf[10..]);
+    assert_eq!(buf, expected);
+    assert_eq!(reader.remaining(), 0);
+
+    let behavior = XofLimitBehavior::default();
+    assert_eq!(behavior, XofLimitBehavior::Panic);
+    let mut reader = TruncatedXof::new(CounterReader::default(), 32, behavior);
+    reader.read(&mut buf[..32]);
+    let res = std::panic::catch_unwind(move || reader.read(&mut [0u8; 1]));
+    assert!(res.is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn alg_name_to_string() {
+    use digest::core_api::alg_name_to_string;
+
+    assert_eq!(alg_name_to_string::<MockCore>(), "Mock");
+    assert_eq!(
+        alg_name_to_string::<CtVariableCoreWrapper<MockCore, U32>>(),
+        "Mock_32"
+    );
+}
+
+#[test]
+fn update_padded() {
+    let mut hasher = MockHash::new();
+    hasher.update(&MSG[..3]);
+    hasher.update_padded(&MSG[3..20]);
+    let mut expected = [0u8; 32];
+    expected[..20].copy_from_slice(&MSG[..20]);
+    assert_eq!(hasher.finalize(), MockHash::digest(expected));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_copy() {
+    let mut hasher = MockHash::new();
+    let n = std::io::copy(&mut &MSG[..], &mut hasher).unwrap();
+    assert_eq!(n, MSG.len() as u64);
+    assert_eq!(hasher.finalize(), MockHash::digest(MSG));
+}
+
+#[test]
+fn prehashed() {
+    use digest::{InvalidBufferLength, Prehashed, Update};
+
+    let digest = MockHash::digest(MSG);
+    let mut prehashed = Prehashed::<MockHash>::default();
+    prehashed.update(&digest[..5]);
+    prehashed.update(&digest[5..]);
+    assert_eq!(prehashed.try_finalize(), Ok(digest));
+    assert_eq!(
+        Prehashed::<MockHash>::from_digest(digest).try_finalize(),
+        Ok(digest)
+    );
+
+    let mut short = Prehashed::<MockHash>::default();
+    short.update(&digest[1..]);
+    assert_eq!(short.try_finalize(), Err(InvalidBufferLength));
+    let mut long = Prehashed::<MockHash>::default();
+    long.update(&digest);
+    long.update(&[0]);
+    assert_eq!(long.try_finalize(), Err(InvalidBufferLength));
+    assert_eq!(
+        Prehashed::<MockHash>::default().try_finalize(),
+        Err(InvalidBufferLength)
+    );
+}
+
+#[test]
+fn chain_update_framed_many() {
+    use digest::Update;
+
+    let res = MockHash::new().chain_update_framed_many(["ab", "c"]);
+    let expected = MockHash::new().chain_framed("ab").chain_framed("c");
+    assert_eq!(res.clone().finalize(), expected.finalize());
+
+    let other = MockHash::new().chain_update_framed_many(["a", "bc"]);
+    assert_ne!(res.finalize(), other.finalize());
+}
+
+#[test]
+fn reset_guard() {
+    use digest::Reset;
+
+    fn hash_checked(hasher: &mut MockHash, data: &[u8]) -> Result<(), ()> {
+        let mut guard = hasher.reset_guard();
+        guard.update(data);
+        if data.is_empty() {
+            return Err(());
+        }
+        Ok(())
+    }
+
+    let mut hasher = MockHash::new();
+    assert!(hash_checked(&mut hasher, MSG).is_ok());
+    assert!(hash_checked(&mut hasher, &[]).is_err());
+    assert_eq!(hasher.finalize(), MockHash::digest([]));
+}
+
+#[test]
+fn update_repeated() {
+    use digest::Update;
+
+    for &count in [0, 1, 63, 64, 65, 1000].iter() {
+        let mut hasher = MockHash::new();
+        hasher.update_repeated(0x5A, count);
+        assert_eq!(hasher.finalize(), MockHash::digest(vec![0x5A; count]));
+    }
+}
+
+#[test]
+fn extend() {
+    let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+
+    let mut hasher = MockHash::new();
+    hasher.extend(data.iter().copied());
+    assert_eq!(hasher.finalize(), MockHash::digest(&data));
+
+    let mut hasher = MockHash::new();
+    hasher.extend(data.chunks(7));
+    assert_eq!(hasher.finalize(), MockHash::digest(&data));
+}
+
+#[test]
+fn collect_digest() {
+    let res = digest::collect_digest::<MockHash, _, _>(MSG.chunks(5));
+    assert_eq!(res, MockHash::digest(MSG));
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn ct_eq() {
+    let a = MockHash::digest(MSG);
+    let mut b = a;
+    assert!(bool::from(digest::ct_eq::<MockHash>(&a, &b)));
+    b[31] ^= 1;
+    assert!(!bool::from(digest::ct_eq::<MockHash>(&a, &b)));
+}
+
+#[test]
+fn masked_update() {
+    use digest::{FixedOutput, MaskedUpdate, Update};
+
+    let mask = b"key";
+    let masked: Vec<u8> = MSG
+        .iter()
+        .zip(mask.iter().cycle())
+        .map(|(a, b)| a ^ b)
+        .collect();
+
+    let mut hasher = MaskedUpdate::new(MockHash::new(), mask).unwrap();
+    hasher.update(&MSG[..4]);
+    hasher.update(&MSG[4..]);
+    assert_eq!(hasher.finalize_fixed(), MockHash::digest(&masked));
+    assert!(MaskedUpdate::new(MockHash::new(), &[]).is_err());
+}
+
+#[test]
+fn get_core() {
+    let mut hasher = MockHash::new();
+    let core = hasher.get_core().clone();
+    hasher.update(&MSG[..10]);
+    assert!(hasher.get_core() == &core);
+    hasher.update(&MSG[10..20]);
+    assert!(hasher.get_core() != &core);
+    *hasher.get_core_mut() = core;
+}
+
+#[test]
+fn finalize_into_slice() {
+    let hasher = MockHash::new().chain_update(MSG);
+    let mut out = [0u8; 32];
+    hasher.clone().finalize_into_slice(&mut out).unwrap();
+    assert_eq!(out[..], MockHash::digest(MSG)[..]);
+    assert!(hasher.finalize_into_slice(&mut [0u8; 31]).is_err());
+}
+
+#[test]
+fn finalize_clone() {
+    use digest::FixedOutput;
+
+    let mut hasher = MockHash::new().chain_update(&MSG[..10]);
+    assert_eq!(hasher.finalize_clone(), MockHash::digest(&MSG[..10]));
+    hasher.update(&MSG[10..]);
+    assert_eq!(hasher.finalize_clone(), MockHash::digest(MSG));
+}
+
+#[test]
+fn ordered_output() {
+    use digest::OrderedOutput;
+    use std::collections::{BTreeSet, HashSet};
+
+    let outputs: Vec<OrderedOutput<MockHash>> =
+        (0u8..8).map(|i| MockHash::digest([i]).into()).collect();
+
+    let mut sorted = outputs.clone();
+    sorted.sort();
+    assert!(sorted.windows(2).all(|w| w[0].as_ref() < w[1].as_ref()));
+
+    let tree: BTreeSet<_> = outputs.iter().cloned().collect();
+    assert!(tree.iter().eq(sorted.iter()));
+
+    let mut set: HashSet<_> = outputs.into_iter().collect();
+    assert_eq!(set.len(), 8);
+    assert!(!set.insert(MockHash::digest([3]).into()));
+}
+
+#[test]
+fn digest_into_slice() {
+    let mut out = [0u8; 32];
+    MockHash::digest_into_slice(MSG, &mut out).unwrap();
+    assert_eq!(out[..], MockHash::digest(MSG)[..]);
+    assert!(MockHash::digest_into_slice(MSG, &mut [0u8; 33]).is_err());
+}
+
+#[test]
+fn domain_separator() {
+    use digest::{DomainSeparator, Update};
+
+    let res = DomainSeparator::<MockHash>::new()
+        .tag("proto")
+        .field(b"ab")
+        .u64(42)
+        .finalize();
+
+    let mut hasher = MockHash::new();
+    Update::update(&mut hasher, &[3]);
+    hasher.update_framed(b"proto");
+    Update::update(&mut hasher, &[1]);
+    hasher.update_framed(b"ab");
+    Update::update(&mut hasher, &[2]);
+    hasher.update_u64_le(42);
+    assert_eq!(res, hasher.finalize());
+
+    let a = DomainSeparator::<MockHash>::new().field(b"ab").field(b"c");
+    let b = DomainSeparator::<MockHash>::new().field(b"a").field(b"bc");
+    assert_ne!(a.finalize(), b.finalize());
+    let a = DomainSeparator::<MockHash>::new().tag("a");
+    let b = DomainSeparator::<MockHash>::new().field(b"a");
+    assert_ne!(a.finalize(), b.finalize());
+}
+
+#[test]
+fn into_parts() {
+    let mut hasher = MockHash::new();
+    Digest::update(&mut hasher, &MSG[..20]);
+    let (core, block, pos) = hasher.into_parts();
+    assert_eq!(pos, 4);
+    assert_eq!(&block[..pos], &MSG[16..20]);
+    assert!(block[pos..].iter().all(|&b| b == 0));
+
+    assert!(MockHash::from_parts(core.clone(), block, 16).is_err());
+    let mut hasher = MockHash::from_parts(core, block, pos).unwrap();
+    Digest::update(&mut hasher, &MSG[20..]);
+    assert_eq!(hasher.finalize(), MockHash::digest(MSG));
+}
+
+#[test]
+fn update_by_ref() {
+    use digest::Update;
+
+    fn feed(mut hasher: impl Update, data: &[u8]) {
+        hasher.update(data);
+    }
+
+    let mut hasher = MockHash::new();
+    feed(&mut hasher, &MSG[..10]);
+    let dyn_hasher: &mut dyn Update = &mut hasher;
+    feed(dyn_hasher, &MSG[10..]);
+    assert_eq!(hasher.finalize(), MockHash::digest(MSG));
+}
+
+#[cfg(feature = "const-generics")]
+#[test]
+fn to_array() {
+    let out = MockHash::digest(MSG);
+    let arr: [u8; 32] = digest::to_array::<MockHash, 32>(&out).unwrap();
+    assert_eq!(arr[..], out[..]);
+    assert!(digest::to_array::<MockHash, 31>(&out).is_none());
+}
+
+#[test]
+fn take() {
+    let mut hasher = MockHash::new();
+    Digest::update(&mut hasher, MSG);
+    assert_eq!(hasher.take(), MockHash::digest(MSG));
+    Digest::update(&mut hasher, &MSG[..5]);
+    assert_eq!(hasher.take(), MockHash::digest(&MSG[..5]));
+    assert_eq!(hasher.finalize(), MockHash::digest(b""));
+}
+
+#[test]
+fn chain_update_if() {
+    use digest::Update;
+
+    let res = MockHash::new()
+        .chain_update_if(true, &MSG[..10])
+        .chain_update_if(false, b"optional")
+        .chain_update_if(true, &MSG[10..])
+        .finalize();
+    assert_eq!(res, MockHash::digest(MSG));
+}
+
+#[test]
+fn multi_digest() {
+    use digest::{consts::U16, MultiDigest2, MultiDigest3, Reset, Update};
+
+    type MockHash16 = CoreWrapper<CtVariableCoreWrapper<MockCore, U16>>;
+
+    let mut hasher = MultiDigest2::<MockHash, MockHash16>::new();
+    hasher.update(&MSG[..10]);
+    hasher.update(&MSG[10..]);
+    let (a, b) = hasher.finalize();
+    assert_eq!(a, MockHash::digest(MSG));
+    assert_eq!(b, MockHash16::digest(MSG));
+
+    let mut hasher = MultiDigest3::<MockHash, MockHash16, MockHash>::new();
+    hasher.update(b"garbage");
+    hasher.reset();
+    hasher.update(MSG);
+    let (a, b, c) = hasher.finalize();
+    assert_eq!((a, b), (MockHash::digest(MSG), MockHash16::digest(MSG)));
+    assert_eq!(c, MockHash::digest(MSG));
+}
+
+#[test]
+fn fmt_write() {
+    use core::fmt::Write;
+
+    let mut hasher = MockHash::new();
+    let name = "hello";
+    write!(hasher, "{} {}-{:02}", name, 42, 7).unwrap();
+    assert_eq!(hasher.finalize(), MockHash::digest("hello 42-07"));
+}
+
+#[test]
+fn reset_to_keyed() {
+    let key = b"secret key";
+    let mut hasher = MockHash::new();
+    for msg in [&MSG[..10], MSG, b""].iter() {
+        hasher.reset_to_keyed(key);
+        Digest::update(&mut hasher, msg);
+        let expected = MockHash::new().chain_update(key).chain_update(msg);
+        assert_eq!(hasher.clone().finalize(), expected.finalize());
+    }
+}
+
+#[test]
+fn output_from_slice() {
+    use digest::output_from_slice;
+
+    let digest = MockHash::digest(MSG);
+    assert_eq!(output_from_slice::<MockHash>(&digest), Ok(digest));
+    assert!(output_from_slice::<MockHash>(&digest[1..]).is_err());
+}
+
+#[test]
+fn customized_xof() {
+    use digest::{CustomizedXof, ExtendableOutput, Reset, Update, XofReader};
+    use mock::MockXofCore;
+
+    type MockXof = CoreWrapper<MockXofCore>;
+
+    let mut expected = [0u8; 40];
+    let prefixed = MockXof::default().chain_framed("name").chain_framed("");
+    prefixed
+        .chain_framed(MSG)
+        .finalize_xof()
+        .read(&mut expected);
+
+    let mut xof = CustomizedXof::<MockXof>::new(b"name", b"");
+    xof.update_framed(b"garbage");
+    xof.reset();
+    xof.update_framed(MSG);
+    let mut out = [0u8; 40];
+    xof.finalize_xof_reset_into(&mut out);
+    assert_eq!(out, expected);
+
+    let mut other = [0u8; 40];
+    CustomizedXof::<MockXof>::new(b"name", b"custom")
+        .chain_framed(MSG)
+        .finalize_xof()
+        .read(&mut other);
+    assert_ne!(out, other);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn hash_many() {
+    let inputs: Vec<&[u8]> = vec![MSG, b"", &MSG[..16], &MSG[5..]];
+    let res = digest::hash_many::<MockHash, _, _>(&inputs);
+    assert_eq!(res.len(), inputs.len());
+    for (input, res) in inputs.iter().zip(res.iter()) {
+        assert_eq!(&MockHash::digest(input), res);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn hash_many_batch() {
+    let records: Vec<Vec<u8>> = (0..10_000u32)
+        .map(|i| i.to_le_bytes().repeat(1 + i as usize % 8))
+        .collect();
+    let res = digest::hash_many::<MockHash, _, _>(&records);
+    assert_eq!(res.len(), records.len());
+    assert_eq!(res[9_999], MockHash::digest(&records[9_999]));
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn xof_rng() {
+    use digest::{rand_core::RngCore, XofReader, XofRng};
+    use mock::CounterReader;
+
+    let mut rng = XofRng::new(CounterReader::default());
+    assert_eq!(rng.next_u32(), u32::from_le_bytes([0, 1, 2, 3]));
+    let mut buf = [0u8; 4];
+    rng.fill_bytes(&mut buf);
+    let mut reader = rng.into_inner();
+    assert_eq!(buf, [4, 5, 6, 7]);
+    reader.fill_bytes(&mut buf);
+    assert_eq!(buf, [8, 9, 10, 11]);
+}
+
+#[test]
+fn update_ints() {
+    use digest::Update;
+
+    let res = MockHash::new()
+        .chain_u16_le(0x0102)
+        .chain_u32_be(0x0304_0506)
+        .chain_u64_le(0x0708_090a_0b0c_0d0e)
+        .chain_u128_be(0x0f);
+    let mut expected = vec![0x02, 0x01, 0x03, 0x04, 0x05, 0x06];
+    expected.extend_from_slice(&[0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08, 0x07]);
+    expected.extend_from_slice(&[0; 15]);
+    expected.push(0x0f);
+    assert_eq!(res.finalize(), MockHash::digest(&expected));
+
+    let mut hasher = MockHash::new();
+    hasher.update_u16_be(0x0102);
+    hasher.update_u32_le(0x0304_0506);
+    hasher.update_u64_be(0x0708);
+    hasher.update_u128_le(1);
+    let mut expected = vec![0x01, 0x02, 0x06, 0x05, 0x04, 0x03];
+    expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0x07, 0x08, 1]);
+    expected.extend_from_slice(&[0; 15]);
+    assert_eq!(hasher.finalize(), MockHash::digest(&expected));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn tee_writer() {
+    use digest::TeeWriter;
+    use std::io::Write;
+
+    let mut writer = TeeWriter::new(Vec::new(), MockHash::new());
+    writer.write_all(&MSG[..10]).unwrap();
+    writer.write_all(&MSG[10..]).unwrap();
+    let (data, res) = writer.finish();
+    assert_eq!(data, MSG);
+    assert_eq!(res, MockHash::digest(MSG));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hashing_reader() {
+    use digest::HashingReader;
+    use std::io::Read;
+
+    let mut reader = HashingReader::new(MSG, MockHash::new());
+    let mut buf = [0u8; 10];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, MSG[..10]);
+    let (rest, res) = reader.finish();
+    assert_eq!(rest, &MSG[10..]);
+    assert_eq!(res, MockHash::digest(&MSG[..10]));
+}
+
+#[test]
+fn display() {
+    assert_eq!(MockHash::new().to_string(), "Mock_32");
+}
+
+#[test]
+fn reset_from() {
+    use digest::ResetFrom;
+
+    let warm = MockHash::new().chain_update(&MSG[..20]);
+    let mut hasher = MockHash::new().chain_update(b"garbage");
+    hasher.reset_from(&warm);
+    hasher.update(&MSG[20..]);
+    assert_eq!(hasher.finalize(), MockHash::digest(MSG));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn update_vectored() {
+    use digest::Update;
+    use std::io::IoSlice;
+
+    let bufs = [
+        IoSlice::new(&MSG[..7]),
+        IoSlice::new(&[]),
+        IoSlice::new(&MSG[7..]),
+    ];
+    let mut hasher = MockHash::new();
+    hasher.update_vectored(&bufs);
+    assert_eq!(hasher.finalize(), MockHash::digest(MSG));
+}
+
+#[test]
+fn update_aligned() {
+    let mut hasher = MockHash::new();
+    assert_eq!(hasher.update_aligned(&MSG[..5]), &MSG[..5]);
+    hasher.update(&MSG[..5]);
+    let rest = hasher.update_aligned(&MSG[5..]);
+    assert_eq!(rest, &MSG[32..]);
+    assert_eq!(hasher.update_aligned(rest), rest);
+    hasher.update(rest);
+    assert_eq!(hasher.finalize(), MockHash::digest(MSG));
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "core-api")]
+
+mod mock;
+
+use digest::{
+    core_api::{hmac, CoreWrapper},
+    Digest,
+};
+use mock::sha256::Sha256Core;
+
+type Sha256 = CoreWrapper<Sha256Core>;
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn sha256_core() {
+    assert_eq!(
+        Sha256::digest(b"abc")[..],
+        unhex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")[..],
+    );
+}
+
+/// Test cases from RFC 4231.
+#[test]
+fn hmac_sha256_rfc4231() {
+    let long_key = [0xaa; 131];
+    let cases: &[(&[u8], &[u8], &str)] = &[
+        (
+            &[0x0b; 20],
+            b"Hi There",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        ),
+        (
+            b"Jefe",
+            b"what do ya want for nothing?",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        ),
+        (
+            &[0xaa; 20],
+            &[0xdd; 50],
+            "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+        ),
+        (
+            &long_key,
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        ),
+        (
+            &long_key,
+            b"This is a test using a larger than block-size key and a larger \
+            than block-size data. The key needs to be hashed before being used \
+            by the HMAC algorithm.",
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+        ),
+    ];
+
+    for (key, msg, expected) in cases {
+        assert_eq!(hmac::<Sha256Core>(key, msg)[..], unhex(expected)[..]);
+    }
+}
@@ -0,0 +1,268 @@
+//! Mock hash function cores used in tests.
+//!
+//! Cores defined in this module are NOT cryptographically secure and
+//! should be used only for testing of the wrapper types.
+#![allow(dead_code)]
+
+pub mod sha256;
+
+use core::fmt;
+use digest::{
+    block_buffer::BlockBuffer,
+    consts::{U16, U64},
+    core_api::{
+        AlgorithmName, ExtendableOutputCore, UpdateCore, VariableOutputCore, XofReaderCore,
+    },
+    generic_array::GenericArray,
+    HashMarker, InvalidOutputSize, Reset, XofReader, XofReaderSeek,
+};
+use serde::{Deserialize, Serialize};
+
+const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a based variable output core.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockCore {
+    state: u64,
+    blocks: u64,
+}
+
+impl MockCore {
+    fn compress(&mut self, block: &[u8]) {
+        for &b in block {
+            self.state = (self.state ^ u64::from(b)).wrapping_mul(PRIME);
+        }
+    }
+}
+
+impl UpdateCore for MockCore {
+    type BlockSize = U16;
+    type Buffer = BlockBuffer<U16>;
+
+    fn update_blocks(&mut self, blocks: &[GenericArray<u8, U16>]) {
+        for block in blocks {
+            self.compress(block);
+        }
+        self.blocks += blocks.len() as u64;
+    }
+}
+
+impl VariableOutputCore for MockCore {
+    type MaxOutputSize = U64;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        if output_size == 0 || output_size > 64 {
+            return Err(InvalidOutputSize::new(output_size, 64));
+        }
+        Ok(Self {
+            state: OFFSET ^ output_size as u64,
+            blocks: 0,
+        })
+    }
+
+    fn finalize_variable_core(
+        &mut self,
+        buffer: &mut Self::Buffer,
+        output_size: usize,
+        f: impl FnOnce(&[u8]),
+    ) {
+        let len = 16 * self.blocks + buffer.get_pos() as u64;
+        let mut state = self.clone();
+        buffer.len64_padding_le(len, |block| state.compress(block));
+
+        let mut out = [0u8; 64];
+        for (i, chunk) in out.chunks_mut(8).enumerate() {
+            let word = (state.state ^ i as u64).wrapping_mul(PRIME);
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        f(&out[..output_size]);
+    }
+}
+
+impl HashMarker for MockCore {}
+
+#[cfg(feature = "zeroize")]
+impl digest::zeroize::Zeroize for MockCore {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.blocks.zeroize();
+    }
+}
+
+impl AlgorithmName for MockCore {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Mock")
+    }
+}
+
+/// Core based on [`MockCore`] which does not implement [`AlgorithmName`].
+#[derive(Clone)]
+pub struct NamelessCore(MockCore);
+
+impl UpdateCore for NamelessCore {
+    type BlockSize = U16;
+    type Buffer = BlockBuffer<U16>;
+
+    fn update_blocks(&mut self, blocks: &[GenericArray<u8, U16>]) {
+        self.0.update_blocks(blocks);
+    }
+}
+
+impl VariableOutputCore for NamelessCore {
+    type MaxOutputSize = U64;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        MockCore::new(output_size).map(Self)
+    }
+
+    fn finalize_variable_core(
+        &mut self,
+        buffer: &mut Self::Buffer,
+        output_size: usize,
+        f: impl FnOnce(&[u8]),
+    ) {
+        self.0.finalize_variable_core(buffer, output_size, f);
+    }
+}
+
+/// Core based on [`MockCore`] which supports only power of two output sizes.
+#[derive(Clone)]
+pub struct Pow2Core(MockCore);
+
+impl UpdateCore for Pow2Core {
+    type BlockSize = U16;
+    type Buffer = BlockBuffer<U16>;
+
+    fn update_blocks(&mut self, blocks: &[GenericArray<u8, U16>]) {
+        self.0.update_blocks(blocks);
+    }
+}
+
+impl VariableOutputCore for Pow2Core {
+    type MaxOutputSize = U64;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        if !output_size.is_power_of_two() {
+            return Err(InvalidOutputSize::new(output_size, 64));
+        }
+        MockCore::new(output_size).map(Self)
+    }
+
+    fn finalize_variable_core(
+        &mut self,
+        buffer: &mut Self::Buffer,
+        output_size: usize,
+        f: impl FnOnce(&[u8]),
+    ) {
+        self.0.finalize_variable_core(buffer, output_size, f);
+    }
+}
+
+/// XOF core based on [`MockCore`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockXofCore(MockCore);
+
+impl Default for MockXofCore {
+    fn default() -> Self {
+        Self(MockCore::new(8).unwrap())
+    }
+}
+
+impl Reset for MockXofCore {
+    fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+impl UpdateCore for MockXofCore {
+    type BlockSize = U16;
+    type Buffer = BlockBuffer<U16>;
+
+    fn update_blocks(&mut self, blocks: &[GenericArray<u8, U16>]) {
+        self.0.update_blocks(blocks);
+    }
+}
+
+impl VariableOutputCore for MockXofCore {
+    type MaxOutputSize = U64;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        MockCore::new(output_size).map(Self)
+    }
+
+    fn finalize_variable_core(
+        &mut self,
+        buffer: &mut Self::Buffer,
+        output_size: usize,
+        f: impl FnOnce(&[u8]),
+    ) {
+        self.0.finalize_variable_core(buffer, output_size, f);
+    }
+}
+
+impl ExtendableOutputCore for MockXofCore {
+    type ReaderCore = MockXofReaderCore;
+
+    fn finalize_xof_core(&mut self, buffer: &mut Self::Buffer) -> MockXofReaderCore {
+        let mut seed = [0u8; 8];
+        self.0
+            .finalize_variable_core(buffer, 8, |res| seed.copy_from_slice(res));
+        MockXofReaderCore {
+            seed: u64::from_le_bytes(seed),
+            counter: 0,
+        }
+    }
+}
+
+impl HashMarker for MockXofCore {}
+
+impl AlgorithmName for MockXofCore {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MockXof")
+    }
+}
+
+/// Reader core of [`MockXofCore`].
+#[derive(Clone, Debug)]
+pub struct MockXofReaderCore {
+    seed: u64,
+    counter: u64,
+}
+
+impl XofReaderCore for MockXofReaderCore {
+    type BlockSize = U16;
+
+    fn read_block(&mut self) -> GenericArray<u8, U16> {
+        let mut block = GenericArray::default();
+        for chunk in block.chunks_mut(8) {
+            let word = (self.seed ^ self.counter).wrapping_mul(PRIME);
+            chunk.copy_from_slice(&word.to_le_bytes());
+            self.counter += 1;
+        }
+        block
+    }
+}
+
+/// XOF reader which outputs byte position modulo 251.
+#[derive(Clone, Debug, Default)]
+pub struct CounterReader(pub u64);
+
+impl XofReader for CounterReader {
+    fn read(&mut self, buffer: &mut [u8]) {
+        for b in buffer.iter_mut() {
+            *b = (self.0 % 251) as u8;
+            self.0 += 1;
+        }
+    }
+}
+
+impl XofReaderSeek for CounterReader {
+    fn position(&self) -> u64 {
+        self.0
+    }
+
+    fn seek(&mut self, pos: u64) {
+        self.0 = pos;
+    }
+}
@@ -0,0 +1,436 @@
+#![cfg(feature = "core-api")]
+
+mod mock;
+
+use digest::{core_api::RtVariableCoreWrapper, Update, VariableOutput};
+use mock::MockCore;
+
+type MockVar = RtVariableCoreWrapper<MockCore>;
+
+const MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_roundtrip() {
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+
+    for split in 0..MSG.len() {
+        let mut hasher = MockVar::new(expected.len()).unwrap();
+        hasher.update(&MSG[..split]);
+
+        let state = serde_json::to_string(&hasher).unwrap();
+        let mut hasher: MockVar = serde_json::from_str(&state).unwrap();
+        hasher.update(&MSG[split..]);
+
+        let mut out = [0u8; 24];
+        hasher.finalize_variable_into(&mut out).unwrap();
+        assert_eq!(out, expected);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_invalid_state() {
+    use digest::core_api::VariableOutputCore;
+
+    let core = serde_json::to_value(MockCore::new(32).unwrap()).unwrap();
+    let too_long = serde_json::json!([core, 32, vec![0u8; 16]]);
+    assert!(serde_json::from_value::<MockVar>(too_long).is_err());
+    for &size in &[0, 65] {
+        let bad_size = serde_json::json!([core, size, [1u8, 2, 3]]);
+        assert!(serde_json::from_value::<MockVar>(bad_size).is_err());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn finalize_variable_vec() {
+    let mut hasher = MockVar::new(24).unwrap();
+    hasher.update(MSG);
+    let mut expected = Vec::new();
+    hasher
+        .clone()
+        .finalize_variable(|res| expected.extend_from_slice(res));
+    assert_eq!(hasher.finalize_variable_vec(), expected);
+}
+
+#[test]
+fn try_into_fixed() {
+    use digest::{
+        consts::{U24, U32},
+        Digest,
+    };
+
+    let mut hasher = MockVar::new(24).unwrap();
+    hasher.update(&MSG[..20]);
+    let hasher = hasher.try_into_fixed::<U32>().unwrap_err();
+    let fixed = hasher.try_into_fixed::<U24>().unwrap();
+
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+    assert_eq!(fixed.chain_update(&MSG[20..]).finalize()[..], expected[..]);
+}
+
+#[test]
+fn output_size_range() {
+    let range = MockVar::output_size_range();
+    assert_eq!(range, 1..=64);
+    assert!(MockVar::new(*range.start() - 1).is_err());
+    assert!(MockVar::new(*range.end() + 1).is_err());
+}
+
+#[test]
+fn max_output_size() {
+    assert_eq!(MockVar::MAX_OUTPUT_SIZE, 64);
+    for &size in [1, 24, 64].iter() {
+        assert_eq!(MockVar::new(size).unwrap().max_output_size(), 64);
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn finalize_variable_heapless() {
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+
+    let mut hasher = MockVar::new(24).unwrap();
+    hasher.update(MSG);
+    assert!(hasher.clone().finalize_variable_heapless::<16>().is_err());
+    let res = hasher.finalize_variable_heapless::<32>().unwrap();
+    assert_eq!(res[..], expected[..]);
+}
+
+#[test]
+fn extend() {
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+
+    let mut hasher = MockVar::new(24).unwrap();
+    hasher.extend(MSG[..10].iter().copied());
+    hasher.extend(MSG[10..].chunks(3));
+    let mut out = [0u8; 24];
+    hasher.finalize_variable_into(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn finalize_boxed_reset() {
+    let mut hasher = MockVar::new(24).unwrap();
+    hasher.update(MSG);
+    let a = hasher.finalize_boxed_reset();
+    hasher.update(MSG);
+    let b = hasher.finalize_boxed_reset();
+    assert_eq!(a.len(), 24);
+    assert_eq!(a, b);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn dyn_variable_output() {
+    use digest::DynVariableOutput;
+
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+
+    let mut hasher: Box<dyn DynVariableOutput> = Box::new(MockVar::new(24).unwrap());
+    assert_eq!(hasher.output_size(), 24);
+    assert_eq!(hasher.max_output_size(), 64);
+
+    hasher.update(&MSG[..10]);
+    hasher.reset();
+    hasher.update(MSG);
+    let mut out = [0u8; 24];
+    assert!(hasher.finalize_variable_reset_into(&mut out[..23]).is_err());
+    hasher.finalize_variable_reset_into(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn dyn_variable_output_boxed() {
+    use digest::DynVariableOutput;
+
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+
+    let mut hasher: Box<dyn DynVariableOutput> = Box::new(MockVar::new(24).unwrap());
+    hasher.update(MSG);
+    assert_eq!(&hasher.box_clone().finalize_boxed()[..], &expected[..]);
+    assert_eq!(&hasher.finalize_boxed_reset()[..], &expected[..]);
+
+    hasher.update(MSG);
+    let res = hasher.clone().finalize_variable_into_boxed(&mut []);
+    assert!(res.is_err());
+    let mut out = [0u8; 24];
+    hasher.finalize_variable_into_boxed(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn new_in_set() {
+    use digest::{InvalidOutputSize, NewInSetError};
+
+    let allowed = [20, 32, 100];
+    assert_eq!(MockVar::new_in_set(32, &allowed).unwrap().output_size(), 32);
+
+    let err = MockVar::new_in_set(24, &allowed).err().unwrap();
+    assert_eq!(err, NewInSetError::NotAllowed { requested: 24 });
+    assert_eq!(err.to_string(), "output size 24 is not in the allowed set");
+
+    let err = MockVar::new_in_set(100, &allowed).err().unwrap();
+    let expected = NewInSetError::Invalid(InvalidOutputSize::new(100, 64));
+    assert_eq!(err, expected);
+}
+
+#[test]
+fn display() {
+    let hasher = MockVar::new(24).unwrap();
+    assert_eq!(hasher.to_string(), "Mock_24");
+    assert_eq!(format!("{:?}", hasher), "Mock { .. }");
+}
+
+#[test]
+fn reset_from() {
+    use digest::ResetFrom;
+
+    let mut warm = MockVar::new(24).unwrap();
+    warm.update(&MSG[..20]);
+    let mut hasher = MockVar::new(32).unwrap();
+    hasher.reset_from(&warm);
+    hasher.update(&MSG[20..]);
+
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+    let mut out = [0u8; 24];
+    hasher.finalize_variable_into(&mut out).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn finalize_xof() {
+    use digest::{core_api::CoreWrapper, ExtendableOutput, XofReader};
+    use mock::MockXofCore;
+
+    let mut hasher = RtVariableCoreWrapper::<MockXofCore>::new(8).unwrap();
+    hasher.update(MSG);
+    let mut reader = hasher.finalize_xof();
+
+    let mut expected = CoreWrapper::<MockXofCore>::default();
+    expected.update(MSG);
+    let mut expected = expected.finalize_xof();
+
+    let (mut a, mut b) = ([0u8; 40], [0u8; 40]);
+    reader.read(&mut a);
+    expected.read(&mut b);
+    assert_eq!(a, b);
+}
+
+#[cfg(feature = "const-generics")]
+#[test]
+fn finalize_variable_array() {
+    use digest::InvalidBufferLength;
+
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+
+    let mut hasher = MockVar::new(24).unwrap();
+    hasher.update(MSG);
+    assert_eq!(hasher.clone().finalize_variable_array::<24>(), Ok(expected));
+    assert_eq!(
+        hasher.clone().finalize_variable_array::<32>(),
+        Err(InvalidBufferLength)
+    );
+    assert_eq!(
+        hasher.finalize_variable_array::<23>(),
+        Err(InvalidBufferLength)
+    );
+}
+
+#[test]
+fn new_for_security_bits() {
+    assert_eq!(
+        MockVar::new_for_security_bits(128).unwrap().output_size(),
+        32
+    );
+    assert_eq!(
+        MockVar::new_for_security_bits(81).unwrap().output_size(),
+        21
+    );
+    assert_eq!(
+        MockVar::new_for_security_bits(256).unwrap().output_size(),
+        64
+    );
+    let err = MockVar::new_for_security_bits(257).unwrap_err();
+    assert_eq!((err.requested(), err.max()), (65, 64));
+}
+
+#[test]
+fn remaining_in_block() {
+    let mut hasher = MockVar::new(24).unwrap();
+    assert_eq!(hasher.remaining_in_block(), 16);
+    hasher.update(&MSG[..5]);
+    assert_eq!(hasher.remaining_in_block(), 11);
+    hasher.update(&MSG[5..16]);
+    assert_eq!(hasher.remaining_in_block(), 16);
+    hasher.update(&MSG[16..35]);
+    assert_eq!(hasher.remaining_in_block(), 13);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize() {
+    use digest::zeroize::Zeroize;
+
+    let mut a = MockVar::new(24).unwrap();
+    a.update(&MSG[..20]);
+    let mut b = MockVar::new(24).unwrap();
+    b.update(&MSG[..7]);
+
+    a.zeroize();
+    b.zeroize();
+    assert_eq!(a.buffered_len(), 0);
+    assert_eq!(a.output_size(), 24);
+
+    let mut out_a = [0u8; 24];
+    let mut out_b = [0u8; 24];
+    a.finalize_variable_into(&mut out_a).unwrap();
+    b.finalize_variable_into(&mut out_b).unwrap();
+    assert_eq!(out_a, out_b);
+}
+
+#[test]
+fn buffered_len() {
+    let mut hasher = MockVar::new(24).unwrap();
+    assert_eq!(hasher.block_size(), 16);
+    assert_eq!(hasher.buffered_len(), 0);
+    hasher.update(&MSG[..5]);
+    assert_eq!(hasher.buffered_len(), 5);
+    hasher.update(&MSG[5..16]);
+    assert_eq!(hasher.buffered_len(), 0);
+    hasher.update(&MSG[16..35]);
+    assert_eq!(hasher.buffered_len(), 3);
+}
+
+#[test]
+fn opaque_debug() {
+    use digest::core_api::OpaqueDebug;
+    use mock::NamelessCore;
+
+    #[derive(Debug)]
+    struct Holder {
+        hasher: OpaqueDebug<NamelessCore>,
+    }
+
+    let mut holder = Holder {
+        hasher: OpaqueDebug(RtVariableCoreWrapper::new(24).unwrap()),
+    };
+    holder.hasher.update(MSG);
+    assert_eq!(
+        format!("{:?}", holder),
+        "Holder { hasher: RtVariableCoreWrapper { output_size: 24, .. } }",
+    );
+
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable(MSG, &mut expected).unwrap();
+    let mut res = [0u8; 24];
+    holder
+        .hasher
+        .into_inner()
+        .finalize_variable_into(&mut res)
+        .unwrap();
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn zero_output_size() {
+    let err = MockVar::new(0).unwrap_err();
+    assert_eq!((err.requested(), err.max()), (0, 64));
+
+    let mut hasher = MockVar::new(24).unwrap();
+    assert!(hasher.set_output_size(0).is_err());
+    assert_eq!(hasher.output_size(), 24);
+}
+
+#[test]
+fn fmt_write() {
+    use core::fmt::Write;
+
+    let mut expected = [0u8; 24];
+    MockVar::digest_variable("value: 42", &mut expected).unwrap();
+
+    let mut hasher = MockVar::new(24).unwrap();
+    write!(hasher, "value: {}", 42).unwrap();
+    let mut res = [0u8; 24];
+    hasher.finalize_variable_into(&mut res).unwrap();
+    assert_eq!(res, expected);
+}
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+fn noop_context<R>(f: impl FnOnce(&mut core::task::Context<'_>) -> R) -> R {
+    use core::task::{Context, RawWaker, RawWakerVTable, Waker};
+
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    let waker = unsafe { Waker::from_raw(clone(core::ptr::null())) };
+    f(&mut Context::from_waker(&waker))
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn futures_io_async_write() {
+    use core::{pin::Pin, task::Poll};
+    use futures_io::AsyncWrite;
+
+    let mut expected = MockVar::new(24).unwrap();
+    expected.update(MSG);
+
+    let mut hasher = MockVar::new(24).unwrap();
+    noop_context(|cx| {
+        for chunk in MSG.chunks(7) {
+            let res = Pin::new(&mut hasher).poll_write(cx, chunk);
+            assert!(matches!(res, Poll::Ready(Ok(n)) if n == chunk.len()));
+        }
+        let res = Pin::new(&mut hasher).poll_flush(cx);
+        assert!(matches!(res, Poll::Ready(Ok(()))));
+        let res = Pin::new(&mut hasher).poll_close(cx);
+        assert!(matches!(res, Poll::Ready(Ok(()))));
+    });
+
+    let (mut a, mut b) = ([0u8; 24], [0u8; 24]);
+    hasher.finalize_variable_into(&mut a).unwrap();
+    expected.finalize_variable_into(&mut b).unwrap();
+    assert_eq!(a, b);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn tokio_async_write() {
+    use core::{pin::Pin, task::Poll};
+    use tokio::io::AsyncWrite;
+
+    let mut expected = MockVar::new(24).unwrap();
+    expected.update(MSG);
+
+    let mut hasher = MockVar::new(24).unwrap();
+    noop_context(|cx| {
+        for chunk in MSG.chunks(7) {
+            let res = Pin::new(&mut hasher).poll_write(cx, chunk);
+            assert!(matches!(res, Poll::Ready(Ok(n)) if n == chunk.len()));
+        }
+        let res = Pin::new(&mut hasher).poll_flush(cx);
+        assert!(matches!(res, Poll::Ready(Ok(()))));
+        let res = Pin::new(&mut hasher).poll_shutdown(cx);
+        assert!(matches!(res, Poll::Ready(Ok(()))));
+    });
+
+    let (mut a, mut b) = ([0u8; 24], [0u8; 24]);
+    hasher.finalize_variable_into(&mut a).unwrap();
+    expected.finalize_variable_into(&mut b).unwrap();
+    assert_eq!(a, b);
+}